@@ -0,0 +1,141 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ff::Field;
+use zkhash::{
+    fields::{goldilocks::FpGoldilocks, utils},
+    monolith::{
+        monolith::Monolith,
+        monolith_instances::{MONOLITH_GOLDILOCKS_PARAMS_T12, MONOLITH_GOLDILOCKS_PARAMS_T8},
+    },
+};
+type Scalar = FpGoldilocks;
+
+// The names are parsed by extract_monolith_bench.py
+fn bench_name(op: &str, t: usize) -> String {
+    if cfg!(feature = "constant-time") {
+        format!("Monolith Goldilocks {} t = {} constant time", op, t)
+    } else {
+        format!("Monolith Goldilocks {} t = {}", op, t)
+    }
+}
+
+fn random_state<const T: usize>() -> [Scalar; T] {
+    let mut state = [Scalar::zero(); T];
+    for el in state.iter_mut() {
+        *el = utils::random_scalar(true);
+    }
+    state
+}
+
+fn monolith_t8(c: &mut Criterion) {
+    let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+    let input = random_state::<8>();
+
+    c.bench_function(&bench_name("Permutation", 8), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Permutation", 8), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation_lookup(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Bars", 8), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Bars", 8), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars_lookup(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Bricks", 8), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bricks(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Concrete", 8), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.concrete(&mut state);
+            black_box(state)
+        });
+    });
+}
+
+fn monolith_t12(c: &mut Criterion) {
+    let monolith = Monolith::<Scalar, 12>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T12);
+    let input = random_state::<12>();
+
+    c.bench_function(&bench_name("Permutation", 12), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Permutation", 12), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation_lookup(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Bars", 12), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Bars", 12), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars_lookup(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Bricks", 12), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bricks(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Concrete", 12), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.concrete(&mut state);
+            black_box(state)
+        });
+    });
+}
+
+fn criterion_benchmark_plain(c: &mut Criterion) {
+    monolith_t8(c);
+    monolith_t12(c);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark_plain
+);
+criterion_main!(benches);
@@ -0,0 +1,141 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ff::Field;
+use zkhash::{
+    fields::{mersenne31::FpMersenne31, utils},
+    monolith::{
+        monolith::Monolith,
+        monolith_instances::{MONOLITH_MERSENNE_PARAMS_T16, MONOLITH_MERSENNE_PARAMS_T24},
+    },
+};
+type Scalar = FpMersenne31;
+
+// The names are parsed by extract_monolith_bench.py
+fn bench_name(op: &str, t: usize) -> String {
+    if cfg!(feature = "constant-time") {
+        format!("Monolith Mersenne {} t = {} constant time", op, t)
+    } else {
+        format!("Monolith Mersenne {} t = {}", op, t)
+    }
+}
+
+fn random_state<const T: usize>() -> [Scalar; T] {
+    let mut state = [Scalar::zero(); T];
+    for el in state.iter_mut() {
+        *el = utils::random_scalar(true);
+    }
+    state
+}
+
+fn monolith_t16(c: &mut Criterion) {
+    let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+    let input = random_state::<16>();
+
+    c.bench_function(&bench_name("Permutation", 16), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Permutation", 16), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation_lookup(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Bars", 16), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Bars", 16), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars_lookup(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Bricks", 16), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bricks(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Concrete", 16), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.concrete(&mut state);
+            black_box(state)
+        });
+    });
+}
+
+fn monolith_t24(c: &mut Criterion) {
+    let monolith = Monolith::<Scalar, 24>::new_wide(&MONOLITH_MERSENNE_PARAMS_T24);
+    let input = random_state::<24>();
+
+    c.bench_function(&bench_name("Permutation", 24), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Permutation", 24), |bench| {
+        bench.iter(|| {
+            let perm = monolith.permutation_lookup(black_box(&input));
+            black_box(perm)
+        });
+    });
+
+    c.bench_function(&bench_name("Bars", 24), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Lookup Bars", 24), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bars_lookup(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Bricks", 24), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.bricks(&mut state);
+            black_box(state)
+        });
+    });
+
+    c.bench_function(&bench_name("Concrete", 24), |bench| {
+        bench.iter(|| {
+            let mut state = black_box(input);
+            monolith.concrete(&mut state);
+            black_box(state)
+        });
+    });
+}
+
+fn criterion_benchmark_plain(c: &mut Criterion) {
+    monolith_t16(c);
+    monolith_t24(c);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark_plain
+);
+criterion_main!(benches);
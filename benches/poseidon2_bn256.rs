@@ -0,0 +1,119 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zkhash::{
+    fields::{bn256::FpBN256, utils},
+    poseidon2::{
+        poseidon2::Poseidon2,
+        poseidon2_instances::{
+            POSEIDON2_BN_PARAMS, POSEIDON2_BN_PARAMS_T2, POSEIDON2_BN_PARAMS_T4,
+        },
+    },
+};
+type Scalar = FpBN256;
+
+fn permutation_t2(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::<Scalar, 2>::new_wide(&POSEIDON2_BN_PARAMS_T2);
+    let input: [Scalar; 2] = [utils::random_scalar(true), utils::random_scalar(true)];
+
+    c.bench_function("Poseidon2 BN256 Permutation t = 2", move |bench| {
+        bench.iter(|| {
+            let perm = poseidon2.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+}
+
+fn permutation_t3(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::<Scalar, 3>::new_wide(&POSEIDON2_BN_PARAMS);
+    let input: [Scalar; 3] = [
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+    ];
+
+    c.bench_function("Poseidon2 BN256 Permutation t = 3", move |bench| {
+        bench.iter(|| {
+            let perm = poseidon2.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+}
+
+fn permutation_t4(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::<Scalar, 4>::new_wide(&POSEIDON2_BN_PARAMS_T4);
+    let input: [Scalar; 4] = [
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+    ];
+
+    c.bench_function("Poseidon2 BN256 Permutation t = 4", move |bench| {
+        bench.iter(|| {
+            let perm = poseidon2.permutation(black_box(&input));
+            black_box(perm)
+        });
+    });
+}
+
+fn hash(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::new(&POSEIDON2_BN_PARAMS);
+    let input1 = utils::random_scalar(true);
+    let input2 = utils::random_scalar(true);
+
+    c.bench_function("Poseidon2 BN256 Hash", move |bench| {
+        bench.iter(|| {
+            let hash = poseidon2.hash(black_box(&input1), black_box(&input2));
+            black_box(hash)
+        });
+    });
+}
+
+fn external_layer(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::new(&POSEIDON2_BN_PARAMS);
+    let input: [Scalar; 3] = [
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+    ];
+
+    c.bench_function("Poseidon2 BN256 External Layer", move |bench| {
+        let mut output = input.to_owned();
+        bench.iter(|| {
+            poseidon2.matmul_external(black_box(&mut output));
+            black_box(&output);
+        });
+    });
+}
+
+fn internal_layer(c: &mut Criterion) {
+    let poseidon2 = Poseidon2::new(&POSEIDON2_BN_PARAMS);
+    let input: [Scalar; 3] = [
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+        utils::random_scalar(true),
+    ];
+
+    c.bench_function("Poseidon2 BN256 Internal Layer", move |bench| {
+        let mut output = input.to_owned();
+        bench.iter(|| {
+            poseidon2.matmul_internal(black_box(&mut output));
+            black_box(&output);
+        });
+    });
+}
+
+fn criterion_benchmark_plain_bn(c: &mut Criterion) {
+    permutation_t2(c);
+    permutation_t3(c);
+    permutation_t4(c);
+    hash(c);
+    external_layer(c);
+    internal_layer(c);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark_plain_bn
+);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use zkhash::{
+    fields::{bn256::FpBN256, utils},
+    reinforced_concrete::{
+        reinforced_concrete::ReinforcedConcrete, reinforced_concrete_instances::RC_BN_PARAMS,
+    },
+};
+type Scalar = FpBN256;
+
+static BATCH_SIZES: [usize; 3] = [16, 256, 4096];
+
+fn random_states(n: usize) -> Vec<[Scalar; 3]> {
+    (0..n)
+        .map(|_| {
+            [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ]
+        })
+        .collect()
+}
+
+fn permutation(c: &mut Criterion) {
+    let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+    let mut group = c.benchmark_group("ReinforcedConcrete BN256 Permutation Batch");
+
+    for size in BATCH_SIZES.iter() {
+        let input = random_states(*size);
+        group.throughput(Throughput::Elements(*size as u64));
+
+        group.bench_with_input(BenchmarkId::new("loop", size), &input, |bench, input| {
+            bench.iter(|| {
+                let perm: Vec<[Scalar; 3]> =
+                    black_box(input).iter().map(|s| rc.permutation(s)).collect();
+                black_box(perm)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("batch", size), &input, |bench, input| {
+            bench.iter(|| {
+                let mut states = black_box(input).to_owned();
+                rc.permutation_batch(&mut states);
+                black_box(states)
+            });
+        });
+    }
+    group.finish();
+}
+
+fn hash(c: &mut Criterion) {
+    let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+    let mut group = c.benchmark_group("ReinforcedConcrete BN256 Hash Batch");
+
+    for size in BATCH_SIZES.iter() {
+        let input: Vec<[Scalar; 2]> = random_states(*size).iter().map(|s| [s[0], s[1]]).collect();
+        group.throughput(Throughput::Elements(*size as u64));
+
+        group.bench_with_input(BenchmarkId::new("batch", size), &input, |bench, input| {
+            bench.iter(|| {
+                let hashes = rc.hash_batch(black_box(input));
+                black_box(hashes)
+            });
+        });
+    }
+    group.finish();
+}
+
+fn criterion_benchmark_batch_bn(c: &mut Criterion) {
+    permutation(c);
+    hash(c);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark_batch_bn
+);
+criterion_main!(benches);
@@ -0,0 +1,146 @@
+// dudect-style timing leakage test of the Bars layer (https://eprint.iacr.org/2016/1123.pdf).
+// Timings of a fixed input are compared against timings of random inputs with
+// Welch's t-test, |t| > 4.5 indicates a data dependent execution time.
+//
+// cargo run --release --example rc_dudect [--features constant-time] [-- <measurements>]
+use ff::PrimeField;
+use rand::{thread_rng, Rng};
+use std::{hint::black_box, sync::Arc, time::Instant};
+use zkhash::{
+    fields::utils,
+    reinforced_concrete::{
+        reinforced_concrete::ReinforcedConcrete,
+        reinforced_concrete_instances::{RC_BLS_PARAMS, RC_BN_PARAMS},
+        reinforced_concrete_params::ReinforcedConcreteParams,
+    },
+};
+
+static DEFAULT_MEASUREMENTS: usize = 200_000;
+static T_THRESHOLD: f64 = 4.5;
+// measurements above these percentiles are discarded, 1.0 keeps everything
+static CROP_PERCENTILES: [f64; 5] = [1.0, 0.99, 0.9, 0.75, 0.5];
+
+#[derive(Default)]
+struct WelchTest {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl WelchTest {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var0 = self.m2[0] / (self.n[0] - 1.0);
+        let var1 = self.m2[1] / (self.n[1] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var0 / self.n[0] + var1 / self.n[1]).sqrt()
+    }
+}
+
+// Times op on every input, where classes[i] is the class of inputs[i], and
+// reports the t statistic for all crop percentiles
+fn leakage<I, O>(name: &str, classes: &[usize], inputs: &[I], op: impl Fn(&I) -> O) {
+    // warm up
+    for input in inputs.iter().take(1000) {
+        black_box(op(black_box(input)));
+    }
+
+    let times: Vec<f64> = inputs
+        .iter()
+        .map(|input| {
+            let start = Instant::now();
+            black_box(op(black_box(input)));
+            start.elapsed().as_nanos() as f64
+        })
+        .collect();
+
+    let mut sorted = times.to_owned();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    println!("  {}:", name);
+    let mut max_t = 0f64;
+    for percentile in CROP_PERCENTILES.iter() {
+        let threshold = sorted[((sorted.len() - 1) as f64 * percentile) as usize];
+        let mut test = WelchTest::default();
+        for (class, time) in classes.iter().zip(times.iter()) {
+            if *time <= threshold {
+                test.push(*class, *time);
+            }
+        }
+        let t = test.t();
+        println!(
+            "    crop at {:>5.1}%: mean fixed {:>8.1}ns, mean random {:>8.1}ns, t = {:>8.2}",
+            percentile * 100.0,
+            test.mean[0],
+            test.mean[1],
+            t
+        );
+        max_t = f64::max(max_t, t.abs());
+    }
+
+    if max_t > T_THRESHOLD {
+        println!("    max |t| = {:.2}: leakage detected", max_t);
+    } else {
+        println!("    max |t| = {:.2}: no leakage detected", max_t);
+    }
+}
+
+// Tests the decomposition into digits, the composition of the S-box outputs
+// and the full Bars layer, which additionally includes the S-box lookups
+fn bars_leakage<F: PrimeField>(
+    name: &str,
+    params: &Arc<ReinforcedConcreteParams<F>>,
+    measurements: usize,
+) {
+    let rc = ReinforcedConcrete::new(params);
+    let mut rng = thread_rng();
+
+    // class 0: fixed input, class 1: random inputs
+    let fixed = [F::zero(); 3];
+    let classes: Vec<usize> = (0..measurements).map(|_| rng.gen_range(0, 2)).collect();
+    let inputs: Vec<[F; 3]> = classes
+        .iter()
+        .map(|class| match class {
+            0 => fixed,
+            _ => [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ],
+        })
+        .collect();
+    let digits: Vec<Vec<u16>> = inputs.iter().map(|input| rc.decompose(&input[0])).collect();
+
+    println!("{}:", name);
+    leakage("decomposition", &classes, &inputs, |input| {
+        rc.decompose(&input[0])
+    });
+    leakage("composition", &classes, &digits, |digits| {
+        rc.compose(digits)
+    });
+    leakage("Bars", &classes, &inputs, |input| rc.bars(input));
+}
+
+fn main() {
+    let measurements = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("measurements has to be a number"))
+        .unwrap_or(DEFAULT_MEASUREMENTS);
+
+    println!(
+        "ReinforcedConcrete Bars timing test, {} measurements, constant-time feature {}",
+        measurements,
+        if cfg!(feature = "constant-time") {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    bars_leakage("BN256", &RC_BN_PARAMS, measurements);
+    bars_leakage("BLS12", &RC_BLS_PARAMS, measurements);
+}
@@ -0,0 +1,11 @@
+// sage:
+// p = 2^64 - 2^32 + 1
+// F = GF(p)
+// F.multiplicative_generator()
+// F(7).is_primitive_root()
+use ff::{Field, PrimeField, PrimeFieldRepr};
+
+#[derive(PrimeField)]
+#[PrimeFieldModulus = "18446744069414584321"]
+#[PrimeFieldGenerator = "7"]
+pub struct FpGoldilocks(FpGoldilocksRepr);
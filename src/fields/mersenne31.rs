@@ -0,0 +1,11 @@
+// sage:
+// p = 2^31 - 1
+// F = GF(p)
+// F.multiplicative_generator()
+// F(7).is_primitive_root()
+use ff::{Field, PrimeField, PrimeFieldRepr};
+
+#[derive(PrimeField)]
+#[PrimeFieldModulus = "2147483647"]
+#[PrimeFieldGenerator = "7"]
+pub struct FpMersenne31(FpMersenne31Repr);
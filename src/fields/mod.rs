@@ -2,6 +2,8 @@
 pub mod bls12;
 #[allow(clippy::too_many_arguments)]
 pub mod bn256;
+pub mod goldilocks;
+pub mod mersenne31;
 pub mod utils;
 // sage:
 // p = 21888242871839275222246405745257275088548364400416034343698204186575808495617
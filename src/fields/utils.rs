@@ -1,4 +1,4 @@
-use ff::{PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+use ff::{LegendreSymbol, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
 use rand::{thread_rng, Rng, SeedableRng, StdRng};
 use sha3::digest::XofReader;
 use std::cmp::min;
@@ -57,7 +57,10 @@ pub fn field_element_from_shake<F: PrimeField>(reader: &mut dyn XofReader) -> F
     let mod_ = F::NUM_BITS % 8;
     let mask = if mod_ == 0 { 0xFF } else { (1u8 << mod_) - 1 };
     let mut buf = vec![0u8; bytes];
-    let mut word_buf = vec![0u64; words];
+    // the repr of 64 bit primes has a spare limb, the upper limbs stay zero
+    let limbs = F::Repr::default().as_ref().len();
+    assert!(words <= limbs);
+    let mut word_buf = vec![0u64; limbs];
 
     let len = buf.len();
     loop {
@@ -150,6 +153,33 @@ pub fn div_mod_crandall<F: PrimeField>(a: &F::Repr, k: u32) -> (F::Repr, u16) {
     (q, r as u16)
 }
 
+// Generalization of div_mod_crandall to divisors 2^k - c with k <= 32 and
+// c < 2^(k-1). Uses a = (a >> k) * (2^k - c) + c * (a >> k) + (a mod 2^k)
+// until the remaining value is below 2^k, i.e., it is fast for small c only.
+#[inline(always)]
+pub fn div_mod_crandall_c<F: PrimeField>(a: &F::Repr, k: u32, c: u32) -> (F::Repr, u32) {
+    debug_assert!(k <= 32 && (c as u64) < (1u64 << (k - 1)));
+    let mask = (1u64 << k) - 1;
+    let divisor = (1u64 << k) - c as u64;
+
+    let mut q = F::Repr::default();
+    let mut x = a.to_owned();
+    while x.as_ref()[0] > mask || x.as_ref().iter().skip(1).any(|x_| *x_ != 0) {
+        let qi = full_shr::<F>(&x, k);
+        let ri = x.as_ref()[0] & mask;
+        partial_add_inplace::<F>(&mut q, &qi);
+        x = mul_by_single_word::<F>(&qi, c as u64);
+        x = add_single_word::<F>(&x, ri);
+    }
+
+    let mut r = x.as_ref()[0];
+    if r >= divisor {
+        r -= divisor;
+        q = add_single_word::<F>(&q, 1);
+    }
+    (q, r as u32)
+}
+
 // -----------------------------------------------------------------------------
 // standard division
 //-----------------------------------------------------------------------------
@@ -231,6 +261,10 @@ pub fn divide_long<F: PrimeField>(a: &F::Repr, divisor: u16) -> (F::Repr, u16) {
 //-----------------------------------------------------------------------------
 
 pub const fn compute_normalized_divisor_and_reciproical(input: u16) -> (u64, u64) {
+    compute_normalized_divisor_and_reciproical_u32(input as u32)
+}
+
+pub const fn compute_normalized_divisor_and_reciproical_u32(input: u32) -> (u64, u64) {
     let s = (input as u64).leading_zeros();
     let normalized_divisor = (input as u64) << s;
     let reciproical = u128::MAX / (normalized_divisor as u128) - (1u128 << 64);
@@ -274,6 +308,29 @@ pub fn divide_long_using_recip<F: PrimeField>(
     recip: u64,
     norm_shift: u32,
 ) -> (F::Repr, u16) {
+    let (result, m) = divide_long_using_recip_word::<F>(a, divisor, recip, norm_shift);
+    (result, m as u16)
+}
+
+// same as divide_long_using_recip for divisors of up to 32 bits
+#[inline(always)]
+pub fn divide_long_using_recip_u32<F: PrimeField>(
+    a: &F::Repr,
+    divisor: u64,
+    recip: u64,
+    norm_shift: u32,
+) -> (F::Repr, u32) {
+    let (result, m) = divide_long_using_recip_word::<F>(a, divisor, recip, norm_shift);
+    (result, m as u32)
+}
+
+#[inline(always)]
+fn divide_long_using_recip_word<F: PrimeField>(
+    a: &F::Repr,
+    divisor: u64,
+    recip: u64,
+    norm_shift: u32,
+) -> (F::Repr, u64) {
     let mut result = F::Repr::default();
     let (repr, mut limb) = full_shl::<F>(a, norm_shift);
 
@@ -288,7 +345,7 @@ pub fn divide_long_using_recip<F: PrimeField>(
             limb = m;
         });
 
-    (result, (limb >> norm_shift) as u16)
+    (result, limb >> norm_shift)
 }
 
 // -----------------------------------------------------------------------------
@@ -512,6 +569,17 @@ mod utils_test_bls12 {
         }
     }
 
+    #[test]
+    fn addition_chain() {
+        for exp in 1..64 {
+            let chain = super::addition_chain(exp);
+            for _ in 0..TESTRUNS {
+                let input: Scalar = random_scalar(true);
+                assert_eq!(pow_addition_chain(&input, &chain), input.pow([exp]));
+            }
+        }
+    }
+
     #[test]
     fn div_equal() {
         let bit: u16 = 10;
@@ -533,6 +601,116 @@ mod utils_test_bls12 {
             assert_eq!(m1, m3);
         }
     }
+
+    #[test]
+    fn div_equal_u32() {
+        let mut rng = thread_rng();
+        for _ in 0..TESTRUNS {
+            let div = rng.gen_range(2u32, u32::MAX);
+            let (divisor, recip) = compute_normalized_divisor_and_reciproical_u32(div);
+            let s = (div as u64).leading_zeros();
+
+            let input: Scalar = random_scalar(true);
+            let repr = input.into_repr();
+            let (res, m) = divide_long_using_recip_u32::<Scalar>(&repr, divisor, recip, s);
+            assert!(m < div);
+            assert_eq!(
+                ct_divide_long_using_recip_u32::<Scalar>(&repr, divisor, recip, s),
+                (res, m)
+            );
+
+            let tmp = mul_by_single_word::<Scalar>(&res, div as u64);
+            let tmp = add_single_word::<Scalar>(&tmp, m as u64);
+            assert_eq!(Scalar::from_repr(tmp).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn div_crandall_c() {
+        let mut rng = thread_rng();
+        for (k, c) in [(10, 1), (10, 3), (20, 1), (20, 17), (32, 5)] {
+            let div = (1u64 << k) - c as u64;
+            for _ in 0..TESTRUNS {
+                let input: Scalar = random_scalar_rng(true, &mut rng);
+                let repr = input.into_repr();
+                let (res, m) = div_mod_crandall_c::<Scalar>(&repr, k, c);
+                assert!((m as u64) < div);
+
+                let tmp = mul_by_single_word::<Scalar>(&res, div);
+                let tmp = add_single_word::<Scalar>(&tmp, m as u64);
+                assert_eq!(Scalar::from_repr(tmp).unwrap(), input);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_division() {
+        let mut rng = thread_rng();
+        for _ in 0..TESTRUNS {
+            let div = rng.gen_range(2u16, u16::MAX);
+            let (divisor, recip) = compute_normalized_divisor_and_reciproical(div);
+            let s = (div as u64).leading_zeros();
+
+            let input: Scalar = random_scalar(true);
+            let repr = input.into_repr();
+            assert_eq!(
+                ct_divide_long_using_recip::<Scalar>(&repr, divisor, recip, s),
+                divide_long_using_recip::<Scalar>(&repr, divisor, recip, s)
+            );
+        }
+    }
+
+    #[test]
+    fn ct_lookup() {
+        let mut rng = thread_rng();
+        let table: Vec<u16> = (0..700).map(|_| rng.gen()).collect();
+        for (i, el) in table.iter().enumerate() {
+            assert_eq!(super::ct_lookup(&table, i as u16), *el);
+        }
+    }
+
+    #[test]
+    fn sum_of_products() {
+        let mut rng = thread_rng();
+        let inv = mont_neg_inv::<Scalar>();
+        let two_128 = super::from_u64::<Scalar>(2).pow([128]);
+        for len in 1..TESTRUNS + 3 {
+            let a: Vec<u64> = (0..len).map(|_| rng.gen::<u64>() >> 1).collect();
+            let w: Vec<Scalar> = (0..len).map(|_| random_scalar(true)).collect();
+            let c: Vec<_> = w
+                .iter()
+                .map(|w_| {
+                    let mut tmp = *w_;
+                    tmp.mul_assign(&two_128);
+                    tmp.into_raw_repr()
+                })
+                .collect();
+
+            let mut expected = Scalar::zero();
+            for (a_, w_) in a.iter().zip(w.iter()) {
+                let mut tmp = super::from_u64::<Scalar>(*a_);
+                tmp.mul_assign(w_);
+                expected.add_assign(&tmp);
+            }
+            let res = sum_of_products_redc::<Scalar>(&a, &c, inv);
+            assert_eq!(Scalar::from_raw_repr(res).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn mont_reduce() {
+        assert!(is_crate_field::<Scalar>());
+        let inv = mont_neg_inv::<Scalar>();
+        let mut minus_one = Scalar::one();
+        minus_one.negate();
+        let mut inputs = vec![Scalar::zero(), Scalar::one(), minus_one];
+        inputs.extend((0..TESTRUNS).map(|_| random_scalar::<Scalar>(true)));
+        for input in inputs {
+            let raw = input.into_raw_repr();
+            assert_eq!(super::mont_reduce::<Scalar>(&raw, inv), input.into_repr());
+            assert_eq!(unsafe { ct_from_raw_repr::<Scalar>(raw) }, input);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -623,6 +801,32 @@ mod utils_test_bn256 {
         }
     }
 
+    #[test]
+    fn addition_chain() {
+        for exp in 1..64 {
+            let chain = super::addition_chain(exp);
+            for _ in 0..TESTRUNS {
+                let input: Scalar = random_scalar(true);
+                assert_eq!(pow_addition_chain(&input, &chain), input.pow([exp]));
+            }
+        }
+    }
+
+    #[test]
+    fn addition_chain_len() {
+        // shorter than the binary chains, e.g., x^15 = (x^3)^4 * x^3
+        for (exp, len) in [(3, 2), (5, 3), (7, 4), (15, 5), (23, 6), (191, 11)] {
+            assert_eq!(super::addition_chain(exp).len(), len);
+        }
+        // sliding windows above SHORTEST_ADDITION_CHAIN_MAX_EXP
+        for exp in [1023, 60000, 65535] {
+            let chain = super::addition_chain(exp);
+            assert!(chain.len() < 2 * (64 - exp.leading_zeros() as usize - 1));
+            let input: Scalar = random_scalar(true);
+            assert_eq!(pow_addition_chain(&input, &chain), input.pow([exp]));
+        }
+    }
+
     #[test]
     fn div_equal() {
         let bit: u16 = 10;
@@ -644,6 +848,116 @@ mod utils_test_bn256 {
             assert_eq!(m1, m3);
         }
     }
+
+    #[test]
+    fn div_equal_u32() {
+        let mut rng = thread_rng();
+        for _ in 0..TESTRUNS {
+            let div = rng.gen_range(2u32, u32::MAX);
+            let (divisor, recip) = compute_normalized_divisor_and_reciproical_u32(div);
+            let s = (div as u64).leading_zeros();
+
+            let input: Scalar = random_scalar(true);
+            let repr = input.into_repr();
+            let (res, m) = divide_long_using_recip_u32::<Scalar>(&repr, divisor, recip, s);
+            assert!(m < div);
+            assert_eq!(
+                ct_divide_long_using_recip_u32::<Scalar>(&repr, divisor, recip, s),
+                (res, m)
+            );
+
+            let tmp = mul_by_single_word::<Scalar>(&res, div as u64);
+            let tmp = add_single_word::<Scalar>(&tmp, m as u64);
+            assert_eq!(Scalar::from_repr(tmp).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn div_crandall_c() {
+        let mut rng = thread_rng();
+        for (k, c) in [(10, 1), (10, 3), (20, 1), (20, 17), (32, 5)] {
+            let div = (1u64 << k) - c as u64;
+            for _ in 0..TESTRUNS {
+                let input: Scalar = random_scalar_rng(true, &mut rng);
+                let repr = input.into_repr();
+                let (res, m) = div_mod_crandall_c::<Scalar>(&repr, k, c);
+                assert!((m as u64) < div);
+
+                let tmp = mul_by_single_word::<Scalar>(&res, div);
+                let tmp = add_single_word::<Scalar>(&tmp, m as u64);
+                assert_eq!(Scalar::from_repr(tmp).unwrap(), input);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_division() {
+        let mut rng = thread_rng();
+        for _ in 0..TESTRUNS {
+            let div = rng.gen_range(2u16, u16::MAX);
+            let (divisor, recip) = compute_normalized_divisor_and_reciproical(div);
+            let s = (div as u64).leading_zeros();
+
+            let input: Scalar = random_scalar(true);
+            let repr = input.into_repr();
+            assert_eq!(
+                ct_divide_long_using_recip::<Scalar>(&repr, divisor, recip, s),
+                divide_long_using_recip::<Scalar>(&repr, divisor, recip, s)
+            );
+        }
+    }
+
+    #[test]
+    fn ct_lookup() {
+        let mut rng = thread_rng();
+        let table: Vec<u16> = (0..700).map(|_| rng.gen()).collect();
+        for (i, el) in table.iter().enumerate() {
+            assert_eq!(super::ct_lookup(&table, i as u16), *el);
+        }
+    }
+
+    #[test]
+    fn sum_of_products() {
+        let mut rng = thread_rng();
+        let inv = mont_neg_inv::<Scalar>();
+        let two_128 = super::from_u64::<Scalar>(2).pow([128]);
+        for len in 1..TESTRUNS + 3 {
+            let a: Vec<u64> = (0..len).map(|_| rng.gen::<u64>() >> 1).collect();
+            let w: Vec<Scalar> = (0..len).map(|_| random_scalar(true)).collect();
+            let c: Vec<_> = w
+                .iter()
+                .map(|w_| {
+                    let mut tmp = *w_;
+                    tmp.mul_assign(&two_128);
+                    tmp.into_raw_repr()
+                })
+                .collect();
+
+            let mut expected = Scalar::zero();
+            for (a_, w_) in a.iter().zip(w.iter()) {
+                let mut tmp = super::from_u64::<Scalar>(*a_);
+                tmp.mul_assign(w_);
+                expected.add_assign(&tmp);
+            }
+            let res = sum_of_products_redc::<Scalar>(&a, &c, inv);
+            assert_eq!(Scalar::from_raw_repr(res).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn mont_reduce() {
+        assert!(is_crate_field::<Scalar>());
+        let inv = mont_neg_inv::<Scalar>();
+        let mut minus_one = Scalar::one();
+        minus_one.negate();
+        let mut inputs = vec![Scalar::zero(), Scalar::one(), minus_one];
+        inputs.extend((0..TESTRUNS).map(|_| random_scalar::<Scalar>(true)));
+        for input in inputs {
+            let raw = input.into_raw_repr();
+            assert_eq!(super::mont_reduce::<Scalar>(&raw, inv), input.into_repr());
+            assert_eq!(unsafe { ct_from_raw_repr::<Scalar>(raw) }, input);
+        }
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -704,3 +1018,527 @@ pub fn mod_inverse<F: PrimeField>(val: u16, modulus: &F::Repr) -> F::Repr {
         prev_a
     }
 }
+
+//-----------------------------------------------------------------------------
+// number theory helpers
+//-----------------------------------------------------------------------------
+pub fn p_minus_1<F: PrimeField>() -> F::Repr {
+    let mut p_1 = F::char();
+    p_1.sub_noborrow(&F::Repr::from(1));
+    p_1
+}
+
+// Euler's criterion
+pub fn legendre<F: PrimeField>(val: &F) -> LegendreSymbol {
+    if val.is_zero() {
+        return LegendreSymbol::Zero;
+    }
+    let exp = full_shr::<F>(&p_minus_1::<F>(), 1);
+    if val.pow(exp) == F::one() {
+        LegendreSymbol::QuadraticResidue
+    } else {
+        LegendreSymbol::QuadraticNonResidue
+    }
+}
+
+pub fn gcd_p_minus_1<F: PrimeField>(val: u16) -> u16 {
+    let (_, mut b) = divide_long::<F>(&p_minus_1::<F>(), val);
+    let mut a = val;
+    while b != 0 {
+        let tmp = a % b;
+        a = b;
+        b = tmp;
+    }
+    a
+}
+
+//-----------------------------------------------------------------------------
+// addition chains
+//-----------------------------------------------------------------------------
+
+// upper bound on the number of steps of an addition chain, which covers all
+// exponents below 2^16
+pub const MAX_ADDITION_CHAIN_LEN: usize = 32;
+// exponents up to this bound get a shortest addition chain from an exhaustive
+// search, larger ones the best sliding window chain
+pub const SHORTEST_ADDITION_CHAIN_MAX_EXP: u64 = 1 << 8;
+
+// Short addition chain for exp. Each step (i, j) multiplies the i-th and j-th
+// intermediate result, the 0-th one being the base.
+pub fn addition_chain(exp: u64) -> Vec<(usize, usize)> {
+    assert!(exp != 0);
+    let mut best = (1..=4)
+        .map(|k| window_addition_chain(exp, k))
+        .min_by_key(|chain| chain.len())
+        .unwrap();
+    if exp <= SHORTEST_ADDITION_CHAIN_MAX_EXP {
+        best = shortest_addition_chain(exp, best.len() - 1);
+    }
+    assert!(
+        best.len() <= MAX_ADDITION_CHAIN_LEN + 1,
+        "addition chain for x^{} is too long",
+        exp
+    );
+
+    // indices of the summands of each element
+    (1..best.len())
+        .map(|k| {
+            (0..k)
+                .flat_map(|i| (0..=i).map(move |j| (i, j)))
+                .find(|(i, j)| best[*i] + best[*j] == best[k])
+                .unwrap()
+        })
+        .collect()
+}
+
+// Sliding window method with windows of up to k bits. Returns the elements of
+// the chain, starting with 1.
+fn window_addition_chain(exp: u64, k: u32) -> Vec<u64> {
+    let mut chain = vec![1u64];
+    if k > 1 {
+        // odd powers up to 2^k - 1
+        chain.push(2);
+        for odd in (3..1u64 << k).step_by(2) {
+            chain.push(odd);
+        }
+    }
+
+    let mut acc = 0u64;
+    let mut bit = 63 - exp.leading_zeros() as i32;
+    while bit >= 0 {
+        if (exp >> bit) & 1 == 0 {
+            acc <<= 1;
+            chain.push(acc);
+            bit -= 1;
+            continue;
+        }
+        // longest window ending in a one
+        let mut low = (bit - k as i32 + 1).max(0);
+        while (exp >> low) & 1 == 0 {
+            low += 1;
+        }
+        let window = (exp >> low) & ((1 << (bit - low + 1)) - 1);
+        if acc != 0 {
+            for _ in low..=bit {
+                acc <<= 1;
+                chain.push(acc);
+            }
+            acc += window;
+        } else {
+            acc = window;
+        }
+        if *chain.last().unwrap() != acc {
+            chain.push(acc);
+        }
+        bit = low - 1;
+    }
+    chain.dedup();
+    chain
+}
+
+// Iterative deepening search for a shortest ascending addition chain with at
+// most max_len steps, which always exists for the length of a known chain
+fn shortest_addition_chain(exp: u64, max_len: usize) -> Vec<u64> {
+    fn search(exp: u64, len: usize, chain: &mut Vec<u64>) -> bool {
+        let last = *chain.last().unwrap();
+        if last == exp {
+            return true;
+        }
+        let remaining = len + 1 - chain.len();
+        // doubling in every remaining step is the fastest growth
+        if remaining == 0 || last << remaining < exp {
+            return false;
+        }
+        for i in (0..chain.len()).rev() {
+            for j in (0..=i).rev() {
+                let next = chain[i] + chain[j];
+                if next <= last {
+                    break;
+                }
+                if next > exp {
+                    continue;
+                }
+                chain.push(next);
+                if search(exp, len, chain) {
+                    return true;
+                }
+                chain.pop();
+            }
+        }
+        false
+    }
+
+    let mut chain = vec![1u64];
+    for len in 0..=max_len {
+        if search(exp, len, &mut chain) {
+            return chain;
+        }
+    }
+    unreachable!("no addition chain with {} steps for x^{}", max_len, exp)
+}
+
+// The intermediate results are kept in a stack buffer, which is large enough
+// for all chains from addition_chain
+#[inline(always)]
+pub fn pow_addition_chain<F: PrimeField>(base: &F, chain: &[(usize, usize)]) -> F {
+    debug_assert!(chain.len() <= MAX_ADDITION_CHAIN_LEN);
+    let mut vals = [*base; MAX_ADDITION_CHAIN_LEN + 1];
+    for (k, (i, j)) in chain.iter().enumerate() {
+        let mut tmp = vals[*i];
+        if i == j {
+            tmp.square();
+        } else {
+            tmp.mul_assign(&vals[*j]);
+        }
+        vals[k + 1] = tmp;
+    }
+    vals[chain.len()]
+}
+
+// x^d with dedicated code for the common exponents 3, 5 and 7, and the addition
+// chain for d otherwise
+#[inline(always)]
+pub fn pow_small<F: PrimeField>(base: &F, d: usize, chain: &[(usize, usize)]) -> F {
+    match d {
+        3 => {
+            let mut out = *base;
+            out.square();
+            out.mul_assign(base);
+            out
+        }
+        5 => {
+            let mut out = *base;
+            out.square();
+            out.square();
+            out.mul_assign(base);
+            out
+        }
+        7 => {
+            let mut base2 = *base;
+            base2.square();
+            let mut out = base2;
+            out.square();
+            out.mul_assign(&base2);
+            out.mul_assign(base);
+            out
+        }
+        _ => pow_addition_chain(base, chain),
+    }
+}
+
+//-----------------------------------------------------------------------------
+// linear layers
+//-----------------------------------------------------------------------------
+
+// Multiplication by the 4x4 matrix
+// (5 7 1 3; 4 6 1 1; 1 3 5 7; 1 1 4 6) with 8 additions and 4 doublings
+pub fn matmul_m4<F: PrimeField>(input: &mut [F]) {
+    let mut t_0 = input[0];
+    t_0.add_assign(&input[1]);
+    let mut t_1 = input[2];
+    t_1.add_assign(&input[3]);
+    let mut t_2 = input[1];
+    t_2.double();
+    t_2.add_assign(&t_1);
+    let mut t_3 = input[3];
+    t_3.double();
+    t_3.add_assign(&t_0);
+    let mut t_4 = t_1;
+    t_4.double();
+    t_4.double();
+    t_4.add_assign(&t_3);
+    let mut t_5 = t_0;
+    t_5.double();
+    t_5.double();
+    t_5.add_assign(&t_2);
+    let mut t_6 = t_3;
+    t_6.add_assign(&t_5);
+    let mut t_7 = t_2;
+    t_7.add_assign(&t_4);
+    input[0] = t_6;
+    input[1] = t_5;
+    input[2] = t_7;
+    input[3] = t_4;
+}
+
+// circ(2, 1) and circ(2, 1, 1) for t = 2 and t = 3, and circ(2 M4, M4, ..., M4)
+// for t = 4k, as used by Poseidon2 and Griffin
+pub fn matmul_circ_m4<F: PrimeField>(state: &mut [F]) {
+    match state.len() {
+        2 | 3 => {
+            let mut sum = state[0];
+            state.iter().skip(1).for_each(|el| sum.add_assign(el));
+            state.iter_mut().for_each(|el| el.add_assign(&sum));
+        }
+        4 => matmul_m4(state),
+        _ => {
+            debug_assert!(state.len().is_multiple_of(4));
+            state.chunks_exact_mut(4).for_each(matmul_m4);
+            let mut sums = [F::zero(); 4];
+            for chunk in state.chunks_exact(4) {
+                for (sum, el) in sums.iter_mut().zip(chunk.iter()) {
+                    sum.add_assign(el);
+                }
+            }
+            for chunk in state.chunks_exact_mut(4) {
+                for (el, sum) in chunk.iter_mut().zip(sums.iter()) {
+                    el.add_assign(sum);
+                }
+            }
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// constant time
+//-----------------------------------------------------------------------------
+
+// all ones if a < b, zero otherwise
+#[inline(always)]
+const fn ct_lt_mask(a: u64, b: u64) -> u64 {
+    let (_, borrow) = a.overflowing_sub(b);
+    0u64.wrapping_sub(borrow as u64)
+}
+
+// Same as div_mod_word_by_short_normalized, with the two correction steps
+// applied via masks instead of branches
+#[inline(always)]
+const fn ct_div_mod_word_by_short_normalized(
+    u1: u64,
+    u0: u64,
+    divisor: u64,
+    recip: u64,
+) -> (u64, u64) {
+    let qq = (u1 as u128) * (recip as u128);
+    let qq = qq + ((u1 as u128) << 64) + (u0 as u128);
+    let (q1, q0) = split(qq);
+    let q1 = q1.wrapping_add(1u64);
+    let r = u0.wrapping_sub(q1.wrapping_mul(divisor));
+
+    // r > q0
+    let mask = ct_lt_mask(q0, r);
+    let q1 = q1.wrapping_add(mask);
+    let r = r.wrapping_add(divisor & mask);
+
+    // r >= divisor
+    let mask = !ct_lt_mask(r, divisor);
+    let q1 = q1.wrapping_sub(mask);
+    let r = r.wrapping_sub(divisor & mask);
+
+    (q1, r)
+}
+
+#[inline(always)]
+pub fn ct_divide_long_using_recip<F: PrimeField>(
+    a: &F::Repr,
+    divisor: u64,
+    recip: u64,
+    norm_shift: u32,
+) -> (F::Repr, u16) {
+    let (result, m) = ct_divide_long_using_recip_word::<F>(a, divisor, recip, norm_shift);
+    (result, m as u16)
+}
+
+#[inline(always)]
+pub fn ct_divide_long_using_recip_u32<F: PrimeField>(
+    a: &F::Repr,
+    divisor: u64,
+    recip: u64,
+    norm_shift: u32,
+) -> (F::Repr, u32) {
+    let (result, m) = ct_divide_long_using_recip_word::<F>(a, divisor, recip, norm_shift);
+    (result, m as u32)
+}
+
+#[inline(always)]
+fn ct_divide_long_using_recip_word<F: PrimeField>(
+    a: &F::Repr,
+    divisor: u64,
+    recip: u64,
+    norm_shift: u32,
+) -> (F::Repr, u64) {
+    let mut result = F::Repr::default();
+    let (repr, mut limb) = full_shl::<F>(a, norm_shift);
+
+    result
+        .as_mut()
+        .iter_mut()
+        .zip(repr.as_ref().iter())
+        .rev()
+        .for_each(|(r, rep)| {
+            let (q, m) = ct_div_mod_word_by_short_normalized(limb, *rep, divisor, recip);
+            *r = q;
+            limb = m;
+        });
+
+    (result, limb >> norm_shift)
+}
+
+// Reads table[index] by scanning the whole table, such that the memory access
+// pattern does not depend on index
+#[inline(always)]
+pub fn ct_lookup(table: &[u16], index: u16) -> u16 {
+    let mut res = 0;
+    for (i, el) in table.iter().enumerate() {
+        // all ones if i == index
+        let mask = (((i as u16 ^ index) as u32).wrapping_sub(1) >> 16) as u16;
+        res |= el & mask;
+    }
+    res
+}
+
+//-----------------------------------------------------------------------------
+// montgomery
+//-----------------------------------------------------------------------------
+
+// -p^-1 mod 2^64
+pub fn mont_neg_inv<F: PrimeField>() -> u64 {
+    let p0 = F::char().as_ref()[0];
+    let mut inv = 1u64;
+    for _ in 0..63 {
+        inv = inv.wrapping_mul(inv);
+        inv = inv.wrapping_mul(p0);
+    }
+    inv.wrapping_neg()
+}
+
+// Computes sum_j a_j * c_j * 2^-128 mod p for a_j < 2^63 and c_j < p, fully
+// reduced. With c_j = w_j * R * 2^128 mod p the result is the Montgomery form
+// of sum_j a_j * w_j, i.e., no further conversion is required. Supports
+// moduli of up to 6 limbs with a free top bit.
+#[inline(always)]
+pub fn sum_of_products_redc<F: PrimeField>(a: &[u64], c: &[F::Repr], inv: u64) -> F::Repr {
+    let p = F::char();
+    let p = p.as_ref();
+    let n = p.len();
+    debug_assert!(n <= 6);
+
+    let mut t = [0u64; 8];
+    for (a_, c_) in a.iter().zip(c.iter()) {
+        debug_assert!(*a_ >> 63 == 0);
+        let (prod, hi) = mul_by_single_word_carry::<F>(c_, *a_);
+        let mut carry = 0u128;
+        for (t_, prod_) in t.iter_mut().zip(prod.as_ref().iter()) {
+            let tmp = *t_ as u128 + *prod_ as u128 + carry;
+            *t_ = tmp as u64;
+            carry = tmp >> 64;
+        }
+        let tmp = t[n] as u128 + hi as u128 + carry;
+        t[n] = tmp as u64;
+        t[n + 1] += (tmp >> 64) as u64;
+    }
+
+    // two montgomery reduction steps, each one divides by 2^64
+    for step in 0..2 {
+        let m = t[step].wrapping_mul(inv);
+        let mut carry = 0u128;
+        for (t_, p_) in t[step..step + n].iter_mut().zip(p.iter()) {
+            let tmp = *t_ as u128 + (m as u128) * (*p_ as u128) + carry;
+            *t_ = tmp as u64;
+            carry = tmp >> 64;
+        }
+        for t_ in t[step + n..].iter_mut() {
+            let tmp = *t_ as u128 + carry;
+            *t_ = tmp as u64;
+            carry = tmp >> 64;
+        }
+    }
+    debug_assert!(t[n + 2..].iter().all(|t_| *t_ == 0));
+
+    // the result is below 2p
+    ct_reduce_once::<F>(&t[2..2 + n])
+}
+
+// Montgomery reduction of a < p, i.e., a * R^-1 mod p. Same as F::into_repr
+// applied to the Montgomery form a, but the final subtraction of p does not
+// branch on the value.
+#[inline(always)]
+pub fn mont_reduce<F: PrimeField>(a: &F::Repr, inv: u64) -> F::Repr {
+    let p = F::char();
+    let p = p.as_ref();
+    let n = p.len();
+    debug_assert!(n <= 6);
+
+    let mut t = [0u64; 13];
+    t[..n].copy_from_slice(a.as_ref());
+    for step in 0..n {
+        let m = t[step].wrapping_mul(inv);
+        let mut carry = 0u128;
+        for (t_, p_) in t[step..step + n].iter_mut().zip(p.iter()) {
+            let tmp = *t_ as u128 + (m as u128) * (*p_ as u128) + carry;
+            *t_ = tmp as u64;
+            carry = tmp >> 64;
+        }
+        for t_ in t[step + n..].iter_mut() {
+            let tmp = *t_ as u128 + carry;
+            *t_ = tmp as u64;
+            carry = tmp >> 64;
+        }
+    }
+    // (a + m * p) / R < p + 1, hence there is no carry into limb 2n
+    debug_assert!(t[2 * n] == 0);
+
+    ct_reduce_once::<F>(&t[n..2 * n])
+}
+
+// t - p if t >= p and t otherwise, for t < 2p, computed with a mask
+#[inline(always)]
+fn ct_reduce_once<F: PrimeField>(t: &[u64]) -> F::Repr {
+    let p = F::char();
+    let mut res = F::Repr::default();
+    let mut sub = F::Repr::default();
+    let mut borrow = 0u64;
+    for (((r, s), t_), p_) in res
+        .as_mut()
+        .iter_mut()
+        .zip(sub.as_mut().iter_mut())
+        .zip(t.iter())
+        .zip(p.as_ref().iter())
+    {
+        *r = *t_;
+        let (tmp, b1) = t_.overflowing_sub(*p_);
+        let (tmp, b2) = tmp.overflowing_sub(borrow);
+        *s = tmp;
+        borrow = (b1 | b2) as u64;
+    }
+    // keep t if t < p
+    let mask = 0u64.wrapping_sub(borrow);
+    for (r, s) in res.as_mut().iter_mut().zip(sub.as_ref().iter()) {
+        *r = (*r & mask) | (*s & !mask);
+    }
+    res
+}
+
+// The prime fields of this crate, which are all derived by ff_ce as newtypes
+// around their repr
+#[cfg(any(test, feature = "constant-time"))]
+pub(crate) fn is_crate_field<F: PrimeField>() -> bool {
+    use super::{
+        bls12::FpBLS12, bn256::FpBN256, goldilocks::FpGoldilocks, mersenne31::FpMersenne31,
+    };
+    use std::any::TypeId;
+
+    let id = TypeId::of::<F>();
+    id == TypeId::of::<FpBN256>()
+        || id == TypeId::of::<FpBLS12>()
+        || id == TypeId::of::<FpGoldilocks>()
+        || id == TypeId::of::<FpMersenne31>()
+}
+
+// Same as F::from_raw_repr for a reduced Montgomery form, but without the
+// validity check, which compares the limbs with early exit.
+//
+// # Safety
+//
+// F has to be one of the prime fields of this crate, see is_crate_field, whose
+// layout is the one of F::Repr. The layout of other PrimeField implementations
+// is unknown. repr has to be a Montgomery form below p.
+#[cfg(any(test, feature = "constant-time"))]
+#[inline(always)]
+pub(crate) unsafe fn ct_from_raw_repr<F: PrimeField>(repr: F::Repr) -> F {
+    debug_assert!(is_crate_field::<F>());
+    assert!(std::mem::size_of::<F>() == std::mem::size_of::<F::Repr>());
+    let res = std::mem::transmute_copy::<F::Repr, F>(&repr);
+    debug_assert!(res.into_raw_repr() == repr);
+    res
+}
@@ -0,0 +1,287 @@
+use super::gmimc_params::GmimcParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Gmimc<F: PrimeField, const T: usize = 3> {
+    pub(crate) params: Arc<GmimcParams<F>>,
+}
+
+impl<F: PrimeField> Gmimc<F> {
+    pub fn new(params: &Arc<GmimcParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Gmimc<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Gmimc::<F, 5>::new_wide(&params) for 4-ary Merkle trees
+    pub fn new_wide(params: &Arc<GmimcParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Gmimc {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    // Unbalanced Feistel network with an expanding round function: the output
+    // of (x_0 + c_r)^d is added to all other branches, followed by a rotation
+    // of the branches. The rotation of the last round is omitted.
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        for rc in self.params.round_constants.iter() {
+            self.round(&mut state, rc);
+            state.rotate_right(1);
+        }
+        state.rotate_left(1);
+        state
+    }
+
+    fn round(&self, state: &mut [F; T], rc: &F) {
+        let mut power = state[0];
+        power.add_assign(rc);
+        power = self.sbox(&power);
+        state
+            .iter_mut()
+            .skip(1)
+            .for_each(|el| el.add_assign(&power));
+    }
+
+    pub fn sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Gmimc<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod gmimc_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        gmimc::gmimc_instances::{
+            GMIMC_BN_PARAMS, GMIMC_BN_PARAMS_T12, GMIMC_BN_PARAMS_T4, GMIMC_BN_PARAMS_T8,
+        },
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn kat<const T: usize>(gmimc: &Gmimc<Scalar, T>) -> Scalar {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        gmimc.permutation(&input)[0]
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let gmimc = Gmimc::new(&GMIMC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = gmimc.permutation(&input1);
+            let perm2 = gmimc.permutation(&input1);
+            let perm3 = gmimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn rounds() {
+        assert_eq!(GMIMC_BN_PARAMS.get_rounds(), 226);
+        assert_eq!(GMIMC_BN_PARAMS_T4.get_rounds(), 228);
+        assert_eq!(GMIMC_BN_PARAMS_T8.get_rounds(), 236);
+        assert_eq!(GMIMC_BN_PARAMS_T12.get_rounds(), 244);
+        assert_eq!(GmimcParams::<Scalar>::compute_rounds(24, 5), 576);
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let gmimc = Gmimc::<Scalar, 4>::new_wide(&GMIMC_BN_PARAMS_T4);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 4];
+            state[..3].copy_from_slice(&input);
+            assert_eq!(gmimc.hash_n(&input), gmimc.permutation(&state)[0]);
+            assert_eq!(
+                gmimc.compress(&[&input[0], &input[1]]),
+                gmimc.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let gmimc = Gmimc::new(&GMIMC_BN_PARAMS);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x059700e50294cd5c81fd641dc0eb49c993cb8b3bce5e37dc03ac8afdae02c964").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 4>::new_wide(&GMIMC_BN_PARAMS_T4);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x02bf331896738995c54bc3c9e14e0d5b8a9a262702d339486230e180454bbf77").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 8>::new_wide(&GMIMC_BN_PARAMS_T8);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x278c9184b322fa3018f3a6d08a27fe9e273181dde408911cc60969b74b1cc77b").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 12>::new_wide(&GMIMC_BN_PARAMS_T12);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x253c1de0c39445549f977fbbc7b3d0e037b5f1cf340b416f72eee1e2c6af5f0d").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod gmimc_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        gmimc::gmimc_instances::{
+            GMIMC_BLS_PARAMS, GMIMC_BLS_PARAMS_T12, GMIMC_BLS_PARAMS_T4, GMIMC_BLS_PARAMS_T8,
+        },
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn kat<const T: usize>(gmimc: &Gmimc<Scalar, T>) -> Scalar {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        gmimc.permutation(&input)[0]
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let gmimc = Gmimc::new(&GMIMC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = gmimc.permutation(&input1);
+            let perm2 = gmimc.permutation(&input1);
+            let perm3 = gmimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn rounds() {
+        assert_eq!(GMIMC_BLS_PARAMS.get_rounds(), 226);
+        assert_eq!(GMIMC_BLS_PARAMS_T4.get_rounds(), 228);
+        assert_eq!(GMIMC_BLS_PARAMS_T8.get_rounds(), 236);
+        assert_eq!(GMIMC_BLS_PARAMS_T12.get_rounds(), 244);
+        assert_eq!(GmimcParams::<Scalar>::compute_rounds(24, 5), 576);
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let gmimc = Gmimc::<Scalar, 4>::new_wide(&GMIMC_BLS_PARAMS_T4);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 4];
+            state[..3].copy_from_slice(&input);
+            assert_eq!(gmimc.hash_n(&input), gmimc.permutation(&state)[0]);
+            assert_eq!(
+                gmimc.compress(&[&input[0], &input[1]]),
+                gmimc.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let gmimc = Gmimc::new(&GMIMC_BLS_PARAMS);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x33345463c7c14698d6f38ea0e444416a2a32ec9f3794c2acfcd9985302c9304d").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 4>::new_wide(&GMIMC_BLS_PARAMS_T4);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x4b70f79dc49b90c0fc5bfa5cf7c85c137e789f381205e83c41b5268d862ff5dc").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 8>::new_wide(&GMIMC_BLS_PARAMS_T8);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x5cdc7ad503f3fa1c3fd4340003f6af5dfcd429b27763eace6ef0cec01f4ac41a").unwrap()
+        );
+        let gmimc = Gmimc::<Scalar, 12>::new_wide(&GMIMC_BLS_PARAMS_T12);
+        assert_eq!(
+            kat(&gmimc),
+            from_hex("0x54b0d2c80caef8312035f57e360b872bec38fb670d6819aaf4653eb1159b0f92").unwrap()
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    gmimc::gmimc_params::GmimcParams,
+};
+
+// x^5 with the number of rounds given by GmimcParams::compute_rounds
+lazy_static! {
+    // BLS12
+    pub static ref GMIMC_BLS_PARAMS: Arc<GmimcParams<FpBLS12>> =
+        Arc::new(GmimcParams::new(3, 5));
+    pub static ref GMIMC_BLS_PARAMS_T4: Arc<GmimcParams<FpBLS12>> =
+        Arc::new(GmimcParams::new(4, 5));
+    pub static ref GMIMC_BLS_PARAMS_T8: Arc<GmimcParams<FpBLS12>> =
+        Arc::new(GmimcParams::new(8, 5));
+    pub static ref GMIMC_BLS_PARAMS_T12: Arc<GmimcParams<FpBLS12>> =
+        Arc::new(GmimcParams::new(12, 5));
+
+    // BN256
+    pub static ref GMIMC_BN_PARAMS: Arc<GmimcParams<FpBN256>> =
+        Arc::new(GmimcParams::new(3, 5));
+    pub static ref GMIMC_BN_PARAMS_T4: Arc<GmimcParams<FpBN256>> =
+        Arc::new(GmimcParams::new(4, 5));
+    pub static ref GMIMC_BN_PARAMS_T8: Arc<GmimcParams<FpBN256>> =
+        Arc::new(GmimcParams::new(8, 5));
+    pub static ref GMIMC_BN_PARAMS_T12: Arc<GmimcParams<FpBN256>> =
+        Arc::new(GmimcParams::new(12, 5));
+}
@@ -0,0 +1,82 @@
+use crate::fields::utils;
+use ff::PrimeField;
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct GmimcParams<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) rounds: usize,
+    // one constant per round, added before the S-box
+    pub(crate) round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> GmimcParams<F> {
+    pub const INIT_SHAKE: &'static str = "GMiMC";
+
+    pub fn new(t: usize, d: usize) -> Self {
+        Self::new_with_rounds(t, d, Self::compute_rounds(t, d))
+    }
+
+    pub fn new_with_rounds(t: usize, d: usize, rounds: usize) -> Self {
+        assert!(t >= 2 && rounds >= 1);
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+
+        let mut shake = Self::init_shake();
+        let round_constants = Self::instantiate_rc(&mut shake, rounds);
+
+        GmimcParams {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds,
+            round_constants,
+        }
+    }
+
+    // The algebraic degree of all branches reaches p after at most
+    // ceil(log_d(p)) + t rounds, which is doubled to resist interpolation and
+    // Groebner basis attacks in both directions. Statistical attacks
+    // require at most t^2 rounds, which only matters for very large t.
+    pub fn compute_rounds(t: usize, d: usize) -> usize {
+        let log_d_p = f64::ceil(F::NUM_BITS as f64 / f64::log2(d as f64)) as usize;
+        let algebraic = 2 * (t + log_d_p);
+        let statistical = t * t;
+        std::cmp::max(algebraic, statistical)
+    }
+
+    fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        for i in F::char().as_ref() {
+            shake.update(&u64::to_le_bytes(*i));
+        }
+        shake.finalize_xof()
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, rounds: usize) -> Vec<F> {
+        (0..rounds)
+            .map(|_| utils::field_element_from_shake(shake))
+            .collect()
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod gmimc;
+pub mod gmimc_instances;
+pub mod gmimc_params;
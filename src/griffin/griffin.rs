@@ -0,0 +1,383 @@
+use super::griffin_params::GriffinParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Griffin<F: PrimeField, const T: usize = 3> {
+    pub(crate) params: Arc<GriffinParams<F>>,
+}
+
+impl<F: PrimeField> Griffin<F> {
+    pub fn new(params: &Arc<GriffinParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Griffin<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Griffin::<F, 4>::new_wide(&params) for 3-ary Merkle trees
+    pub fn new_wide(params: &Arc<GriffinParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Griffin {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+
+        // linear layer at the beginning
+        self.linear_layer(&mut state);
+
+        for r in 0..self.params.rounds {
+            self.non_linear_layer(&mut state);
+            self.linear_layer(&mut state);
+            if r < self.params.rounds - 1 {
+                for (el, rc) in state.iter_mut().zip(self.params.round_constants[r].iter()) {
+                    el.add_assign(rc);
+                }
+            }
+        }
+        state
+    }
+
+    pub fn sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    pub fn sbox_inv(&self, input: &F) -> F {
+        input.pow(self.params.d_inv)
+    }
+
+    // Horst-like layer: y_0 = x_0^(1/d), y_1 = x_1^d and
+    // y_i = x_i * (L_i^2 + alpha_i L_i + beta_i) for i >= 2 with
+    // L_i = (i - 1) y_0 + y_1 + x_(i-1) and L_2 = y_0 + y_1
+    pub fn non_linear_layer(&self, state: &mut [F; T]) {
+        let y0 = self.sbox_inv(&state[0]);
+        let y1 = self.sbox(&state[1]);
+
+        // (i - 1) y_0 + y_1, updated incrementally
+        let mut l_acc = y1;
+        let mut prev = F::zero();
+        for (i, [alpha, beta]) in (2..T).zip(self.params.alpha_beta.iter()) {
+            l_acc.add_assign(&y0);
+            let mut l = l_acc;
+            l.add_assign(&prev);
+            prev = state[i];
+
+            let mut tmp = l;
+            tmp.add_assign(alpha);
+            tmp.mul_assign(&l);
+            tmp.add_assign(beta);
+            state[i].mul_assign(&tmp);
+        }
+
+        state[0] = y0;
+        state[1] = y1;
+    }
+
+    // circ(2, 1, 1) for t = 3 and circ(2 M4, M4, ..., M4) for t = 4k
+    pub fn linear_layer(&self, state: &mut [F; T]) {
+        utils::matmul_circ_m4(state);
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Griffin<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod griffin_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        griffin::griffin_instances::{
+            GRIFFIN_BN_PARAMS, GRIFFIN_BN_PARAMS_T12, GRIFFIN_BN_PARAMS_T4, GRIFFIN_BN_PARAMS_T8,
+        },
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn kat<const T: usize>(griffin: &Griffin<Scalar, T>) -> Scalar {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        griffin.permutation(&input)[0]
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let griffin = Griffin::new(&GRIFFIN_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = griffin.permutation(&input1);
+            let perm2 = griffin.permutation(&input1);
+            let perm3 = griffin.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn sbox_inverse() {
+        let griffin = Griffin::new(&GRIFFIN_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            assert_eq!(griffin.sbox_inv(&griffin.sbox(&input)), input);
+            assert_eq!(griffin.sbox(&griffin.sbox_inv(&input)), input);
+        }
+    }
+
+    #[test]
+    fn non_linear_layer() {
+        let griffin = Griffin::<Scalar, 8>::new_wide(&GRIFFIN_BN_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 8] = [(); 8].map(|_| utils::random_scalar(true));
+            let mut state = input;
+            griffin.non_linear_layer(&mut state);
+
+            assert_eq!(griffin.sbox(&state[0]), input[0]);
+            assert_eq!(state[1], griffin.sbox(&input[1]));
+            for i in 2..8 {
+                let mut l = state[0];
+                l.mul_assign(&utils::from_u64((i - 1) as u64));
+                l.add_assign(&state[1]);
+                if i > 2 {
+                    l.add_assign(&input[i - 1]);
+                }
+                let [alpha, beta] = GRIFFIN_BN_PARAMS_T8.alpha_beta[i - 2];
+                let mut l2 = l;
+                l2.square();
+                let mut al = alpha;
+                al.mul_assign(&l);
+                l2.add_assign(&al);
+                l2.add_assign(&beta);
+                let mut expected = input[i];
+                expected.mul_assign(&l2);
+                assert_eq!(state[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let griffin = Griffin::<Scalar, 4>::new_wide(&GRIFFIN_BN_PARAMS_T4);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 4];
+            state[..3].copy_from_slice(&input);
+            assert_eq!(griffin.hash_n(&input), griffin.permutation(&state)[0]);
+            assert_eq!(
+                griffin.compress(&[&input[0], &input[1]]),
+                griffin.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let griffin = Griffin::new(&GRIFFIN_BN_PARAMS);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x2311cdb3076c3a7ee37fd5a271e0f3a8a3cc38057d0cea37b78951f43b1b6ff6").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 4>::new_wide(&GRIFFIN_BN_PARAMS_T4);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x2d6b8390f84d9f517c5e340ddb7c3125d475bf2c7c6699b457d5437ed5cb0124").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 8>::new_wide(&GRIFFIN_BN_PARAMS_T8);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x2a18bb16722de675f11385220d63f5a31d42bf6620b43487b17a317555c8086f").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 12>::new_wide(&GRIFFIN_BN_PARAMS_T12);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x2a3a98de77aced7aa65d8573aeaa99267a22e54bef807ae43912b9111efe840a").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod griffin_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        griffin::griffin_instances::{
+            GRIFFIN_BLS_PARAMS, GRIFFIN_BLS_PARAMS_T12, GRIFFIN_BLS_PARAMS_T4,
+            GRIFFIN_BLS_PARAMS_T8,
+        },
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn kat<const T: usize>(griffin: &Griffin<Scalar, T>) -> Scalar {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        griffin.permutation(&input)[0]
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let griffin = Griffin::new(&GRIFFIN_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = griffin.permutation(&input1);
+            let perm2 = griffin.permutation(&input1);
+            let perm3 = griffin.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn sbox_inverse() {
+        let griffin = Griffin::new(&GRIFFIN_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            assert_eq!(griffin.sbox_inv(&griffin.sbox(&input)), input);
+            assert_eq!(griffin.sbox(&griffin.sbox_inv(&input)), input);
+        }
+    }
+
+    #[test]
+    fn non_linear_layer() {
+        let griffin = Griffin::<Scalar, 8>::new_wide(&GRIFFIN_BLS_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 8] = [(); 8].map(|_| utils::random_scalar(true));
+            let mut state = input;
+            griffin.non_linear_layer(&mut state);
+
+            assert_eq!(griffin.sbox(&state[0]), input[0]);
+            assert_eq!(state[1], griffin.sbox(&input[1]));
+            for i in 2..8 {
+                let mut l = state[0];
+                l.mul_assign(&utils::from_u64((i - 1) as u64));
+                l.add_assign(&state[1]);
+                if i > 2 {
+                    l.add_assign(&input[i - 1]);
+                }
+                let [alpha, beta] = GRIFFIN_BLS_PARAMS_T8.alpha_beta[i - 2];
+                let mut l2 = l;
+                l2.square();
+                let mut al = alpha;
+                al.mul_assign(&l);
+                l2.add_assign(&al);
+                l2.add_assign(&beta);
+                let mut expected = input[i];
+                expected.mul_assign(&l2);
+                assert_eq!(state[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let griffin = Griffin::<Scalar, 4>::new_wide(&GRIFFIN_BLS_PARAMS_T4);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 4];
+            state[..3].copy_from_slice(&input);
+            assert_eq!(griffin.hash_n(&input), griffin.permutation(&state)[0]);
+            assert_eq!(
+                griffin.compress(&[&input[0], &input[1]]),
+                griffin.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let griffin = Griffin::new(&GRIFFIN_BLS_PARAMS);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x3c87fead8ab715649640b2f0e5357f2fda8e14e69893462780fb60b142901e6a").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 4>::new_wide(&GRIFFIN_BLS_PARAMS_T4);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x0411e6fe246b88171124333e47e9676b3da176cd1970b15732c7b1b35986aaa4").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 8>::new_wide(&GRIFFIN_BLS_PARAMS_T8);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x13bc89b68b1fbea70e5a50b3586f508de62e1719821e9d722cbfef3760a7eba8").unwrap()
+        );
+        let griffin = Griffin::<Scalar, 12>::new_wide(&GRIFFIN_BLS_PARAMS_T12);
+        assert_eq!(
+            kat(&griffin),
+            from_hex("0x47be3c9d23f3ad25d4681f0579ce8bb07ebc4d7faa8b7eaddeedd057cc07203b").unwrap()
+        );
+    }
+}
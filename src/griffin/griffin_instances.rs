@@ -0,0 +1,31 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    griffin::griffin_params::GriffinParams,
+};
+
+// x^5 with the number of rounds proposed in the Griffin paper for 128 bit
+// security
+lazy_static! {
+    // BLS12
+    pub static ref GRIFFIN_BLS_PARAMS: Arc<GriffinParams<FpBLS12>> =
+        Arc::new(GriffinParams::new(3, 5, 12));
+    pub static ref GRIFFIN_BLS_PARAMS_T4: Arc<GriffinParams<FpBLS12>> =
+        Arc::new(GriffinParams::new(4, 5, 11));
+    pub static ref GRIFFIN_BLS_PARAMS_T8: Arc<GriffinParams<FpBLS12>> =
+        Arc::new(GriffinParams::new(8, 5, 9));
+    pub static ref GRIFFIN_BLS_PARAMS_T12: Arc<GriffinParams<FpBLS12>> =
+        Arc::new(GriffinParams::new(12, 5, 9));
+
+    // BN256
+    pub static ref GRIFFIN_BN_PARAMS: Arc<GriffinParams<FpBN256>> =
+        Arc::new(GriffinParams::new(3, 5, 12));
+    pub static ref GRIFFIN_BN_PARAMS_T4: Arc<GriffinParams<FpBN256>> =
+        Arc::new(GriffinParams::new(4, 5, 11));
+    pub static ref GRIFFIN_BN_PARAMS_T8: Arc<GriffinParams<FpBN256>> =
+        Arc::new(GriffinParams::new(8, 5, 9));
+    pub static ref GRIFFIN_BN_PARAMS_T12: Arc<GriffinParams<FpBN256>> =
+        Arc::new(GriffinParams::new(12, 5, 9));
+}
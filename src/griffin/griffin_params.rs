@@ -0,0 +1,113 @@
+use crate::fields::utils;
+use ff::{LegendreSymbol, PrimeField};
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct GriffinParams<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) d_inv: F::Repr,
+    pub(crate) rounds: usize,
+    // (alpha_i, beta_i) = ((i - 1) alpha, (i - 1)^2 beta) for i = 2, ..., t - 1
+    pub(crate) alpha_beta: Vec<[F; 2]>,
+    // no constants are added in the last round
+    pub(crate) round_constants: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> GriffinParams<F> {
+    pub const INIT_SHAKE: &'static str = "Griffin";
+
+    pub fn new(t: usize, d: usize, rounds: usize) -> Self {
+        assert!(
+            t == 3 || (t >= 4 && t.is_multiple_of(4)),
+            "unsupported width {}",
+            t
+        );
+        assert!(rounds >= 1);
+        assert!(
+            d >= 3 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+
+        let mut shake = Self::init_shake();
+        let round_constants = Self::instantiate_rc(&mut shake, t, rounds - 1);
+        let alpha_beta = Self::instantiate_alpha_beta(&mut shake, t);
+
+        GriffinParams {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            d_inv: utils::mod_inverse::<F>(d as u16, &utils::p_minus_1::<F>()),
+            rounds,
+            alpha_beta,
+            round_constants,
+        }
+    }
+
+    fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        for i in F::char().as_ref() {
+            shake.update(&u64::to_le_bytes(*i));
+        }
+        shake.finalize_xof()
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, t: usize, rounds: usize) -> Vec<Vec<F>> {
+        (0..rounds)
+            .map(|_| {
+                (0..t)
+                    .map(|_| utils::field_element_from_shake(shake))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // alpha and beta are sampled until alpha^2 - 4 beta is a quadratic
+    // non-residue, such that the quadratic functions have no roots. Scaling by
+    // (i - 1) and (i - 1)^2 preserves this property.
+    fn instantiate_alpha_beta(shake: &mut dyn XofReader, t: usize) -> Vec<[F; 2]> {
+        let (alpha, beta) = loop {
+            let alpha = utils::field_element_from_shake_without_0::<F>(shake);
+            let beta = utils::field_element_from_shake_without_0::<F>(shake);
+            let mut disc = alpha;
+            disc.square();
+            let mut beta4 = beta;
+            beta4.double();
+            beta4.double();
+            disc.sub_assign(&beta4);
+            if utils::legendre(&disc) == LegendreSymbol::QuadraticNonResidue {
+                break (alpha, beta);
+            }
+        };
+
+        (2..t)
+            .map(|i| {
+                let scale = utils::from_u64::<F>((i - 1) as u64);
+                let mut alpha_i = alpha;
+                alpha_i.mul_assign(&scale);
+                let mut beta_i = beta;
+                beta_i.mul_assign(&scale);
+                beta_i.mul_assign(&scale);
+                [alpha_i, beta_i]
+            })
+            .collect()
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds
+    }
+}
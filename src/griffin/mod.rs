@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod griffin;
+pub mod griffin_instances;
+pub mod griffin_params;
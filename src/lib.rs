@@ -1,11 +1,23 @@
 //! # zkhash
 //!
-//! A pure Rust implementation of the ReinforcedConcrete Permutation
+//! Pure Rust implementations of hash functions for zero-knowledge applications,
+//! such as ReinforcedConcrete, Poseidon, Poseidon2, Rescue-Prime, Griffin,
+//! Neptune, GMiMC, MiMC, Monolith and the Sapling Pedersen hash
 #![cfg_attr(feature = "asm", feature(asm))]
 
 pub extern crate ff;
 
 pub mod fields;
+pub mod gmimc;
+pub mod griffin;
 pub mod merkle_tree;
+pub mod mimc;
+pub mod monolith;
+pub mod neptune;
+pub mod pedersen_hash;
+pub mod poseidon;
+pub mod poseidon2;
 pub mod reinforced_concrete;
+pub mod rescue_prime;
+pub mod sponge;
 pub mod utils;
@@ -1,4 +1,6 @@
 use ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::marker::PhantomData;
 
 pub trait MerkleTreeHash<F: PrimeField> {
@@ -32,7 +34,8 @@ impl<F: PrimeField, P: MerkleTreeHash<F>> MerkleTree<F, P> {
         res
     }
 
-    pub fn accumulate(&mut self, set: &[F]) -> F {
+    // copies the set and pads it with its last element to a power of two
+    fn leaves(set: &[F]) -> Vec<F> {
         let set_size = set.len();
         let mut bound = Self::round_up_pow_n(set_size, 2);
         loop {
@@ -49,7 +52,11 @@ impl<F: PrimeField, P: MerkleTreeHash<F>> MerkleTree<F, P> {
         for _ in nodes.len()..bound {
             nodes.push(nodes[set_size - 1].to_owned());
         }
+        nodes
+    }
 
+    pub fn accumulate(&mut self, set: &[F]) -> F {
+        let mut nodes = Self::leaves(set);
         while nodes.len() > 1 {
             let new_len = nodes.len() / 2;
             let mut new_nodes: Vec<F> = Vec::with_capacity(new_len);
@@ -63,3 +70,65 @@ impl<F: PrimeField, P: MerkleTreeHash<F>> MerkleTree<F, P> {
         nodes[0].to_owned()
     }
 }
+
+#[cfg(feature = "parallel")]
+impl<F: PrimeField, P: MerkleTreeHash<F> + Sync> MerkleTree<F, P> {
+    // Same as accumulate, but the nodes of each level are compressed by
+    // multiple worker threads. Produces the same root as accumulate.
+    pub fn accumulate_parallel(&mut self, set: &[F]) -> F {
+        let mut nodes = Self::leaves(set);
+        while nodes.len() > 1 {
+            nodes = nodes
+                .par_chunks(2)
+                .map(|pair| self.perm.compress(&[&pair[0], &pair[1]]))
+                .collect();
+        }
+        nodes[0].to_owned()
+    }
+}
+
+#[cfg(all(test, feature = "parallel"))]
+mod merkle_tree_fp_tests_bn256 {
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        reinforced_concrete::{
+            reinforced_concrete::ReinforcedConcrete, reinforced_concrete_instances::RC_BN_PARAMS,
+        },
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    #[test]
+    fn parallel() {
+        let mut tree = MerkleTree::new(ReinforcedConcrete::new(&RC_BN_PARAMS));
+        for len in [1, 2, 7, 64, 100] {
+            let set: Vec<Scalar> = (0..len).map(|_| utils::random_scalar(true)).collect();
+            assert_eq!(tree.accumulate_parallel(&set), tree.accumulate(&set));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "parallel"))]
+mod merkle_tree_fp_tests_bls12 {
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        reinforced_concrete::{
+            reinforced_concrete::ReinforcedConcrete, reinforced_concrete_instances::RC_BLS_PARAMS,
+        },
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    #[test]
+    fn parallel() {
+        let mut tree = MerkleTree::new(ReinforcedConcrete::new(&RC_BLS_PARAMS));
+        for len in [1, 2, 7, 64, 100] {
+            let set: Vec<Scalar> = (0..len).map(|_| utils::random_scalar(true)).collect();
+            assert_eq!(tree.accumulate_parallel(&set), tree.accumulate(&set));
+        }
+    }
+}
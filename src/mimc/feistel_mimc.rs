@@ -0,0 +1,256 @@
+use super::mimc_params::MimcParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+// Feistel-MiMC-2n/n
+#[derive(Clone, Debug)]
+pub struct FeistelMimc<F: PrimeField> {
+    pub(crate) params: Arc<MimcParams<F>>,
+}
+
+impl<F: PrimeField> FeistelMimc<F> {
+    pub fn new(params: &Arc<MimcParams<F>>) -> Self {
+        FeistelMimc {
+            params: Arc::clone(params),
+        }
+    }
+
+    // (x_L, x_R) <- (x_R + (x_L + k + c_i)^d, x_L) in each round, without the
+    // swap in the last round
+    pub fn encrypt(&self, input: &[F; 2], key: &F) -> [F; 2] {
+        let [mut l, mut r] = *input;
+        let rounds = self.params.rounds;
+        for (i, rc) in self.params.round_constants.iter().enumerate() {
+            let f = self.round_function(&l, key, rc);
+            r.add_assign(&f);
+            if i < rounds - 1 {
+                std::mem::swap(&mut l, &mut r);
+            }
+        }
+        [l, r]
+    }
+
+    pub fn decrypt(&self, input: &[F; 2], key: &F) -> [F; 2] {
+        let [mut l, mut r] = *input;
+        for (i, rc) in self.params.round_constants.iter().enumerate().rev() {
+            let f = self.round_function(&l, key, rc);
+            r.sub_assign(&f);
+            if i > 0 {
+                std::mem::swap(&mut l, &mut r);
+            }
+        }
+        [l, r]
+    }
+
+    pub fn permutation(&self, input: &[F; 2]) -> [F; 2] {
+        self.encrypt(input, &F::zero())
+    }
+
+    fn round_function(&self, input: &F, key: &F, rc: &F) -> F {
+        let mut state = *input;
+        state.add_assign(key);
+        state.add_assign(rc);
+        self.sbox(&state)
+    }
+
+    pub fn sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    // Sponge with rate and capacity of one element each, the output is x_L
+    pub fn hash_slice(&self, input: &[F]) -> F {
+        let mut state = [F::zero(); 2];
+        for el in input.iter() {
+            state[0].add_assign(el);
+            state = self.permutation(&state);
+        }
+        state[0]
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        self.hash_slice(&[el1.to_owned(), el2.to_owned()])
+    }
+}
+
+impl<F: PrimeField> MerkleTreeHash<F> for FeistelMimc<F> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod feistel_mimc_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        mimc::mimc_instances::FEISTEL_MIMC_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 2] = [utils::random_scalar(true), utils::random_scalar(true)];
+
+            let mut input2: [Scalar; 2];
+            loop {
+                input2 = [utils::random_scalar(true), utils::random_scalar(true)];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = mimc.permutation(&input1);
+            let perm2 = mimc.permutation(&input1);
+            let perm3 = mimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BN_PARAMS);
+        assert_eq!(FEISTEL_MIMC_BN_PARAMS.get_rounds(), 322);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 2] = [utils::random_scalar(true), utils::random_scalar(true)];
+            let key: Scalar = utils::random_scalar(true);
+            let enc = mimc.encrypt(&input, &key);
+            assert_ne!(enc, input);
+            assert_eq!(mimc.decrypt(&enc, &key), input);
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 2];
+            for el in input.iter() {
+                state[0].add_assign(el);
+                state = mimc.permutation(&state);
+            }
+            assert_eq!(mimc.hash_slice(&input), state[0]);
+            assert_eq!(
+                mimc.compress(&[&input[0], &input[1]]),
+                mimc.hash_slice(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BN_PARAMS);
+        let perm = mimc.permutation(&[Scalar::zero(), Scalar::one()]);
+        assert_eq!(
+            perm[0],
+            from_hex("0x03d3b403dac8a66ba410a95c64629b826a084a2f52e1d87a1b0752cf37bcbb83").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x149b4eab4f52189d1059a3760c79fbdcbbc76e9a4b93a6f919928c57985642e4").unwrap()
+        );
+        let enc = mimc.encrypt(&[Scalar::zero(), Scalar::one()], &utils::from_u64(2));
+        assert_eq!(
+            enc[0],
+            from_hex("0x095cd12abdf521b544e0d62707af8f4900033288fa3cca53b5f48cc116ef800f").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod feistel_mimc_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        mimc::mimc_instances::FEISTEL_MIMC_BLS_PARAMS,
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 2] = [utils::random_scalar(true), utils::random_scalar(true)];
+
+            let mut input2: [Scalar; 2];
+            loop {
+                input2 = [utils::random_scalar(true), utils::random_scalar(true)];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = mimc.permutation(&input1);
+            let perm2 = mimc.permutation(&input1);
+            let perm3 = mimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BLS_PARAMS);
+        assert_eq!(FEISTEL_MIMC_BLS_PARAMS.get_rounds(), 322);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 2] = [utils::random_scalar(true), utils::random_scalar(true)];
+            let key: Scalar = utils::random_scalar(true);
+            let enc = mimc.encrypt(&input, &key);
+            assert_ne!(enc, input);
+            assert_eq!(mimc.decrypt(&enc, &key), input);
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 2];
+            for el in input.iter() {
+                state[0].add_assign(el);
+                state = mimc.permutation(&state);
+            }
+            assert_eq!(mimc.hash_slice(&input), state[0]);
+            assert_eq!(
+                mimc.compress(&[&input[0], &input[1]]),
+                mimc.hash_slice(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let mimc = FeistelMimc::new(&FEISTEL_MIMC_BLS_PARAMS);
+        let perm = mimc.permutation(&[Scalar::zero(), Scalar::one()]);
+        assert_eq!(
+            perm[0],
+            from_hex("0x4598e172a539f871e672addc9ef7714c5a61cadd4cb6a02a4f9e0fb9894d681c").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x2d2b64dbf5ef0a9b59d9bf5e23f741ced2ac12928c5bd1c5bf0bdb6c808401af").unwrap()
+        );
+        let enc = mimc.encrypt(&[Scalar::zero(), Scalar::one()], &utils::from_u64(2));
+        assert_eq!(
+            enc[0],
+            from_hex("0x6d7249b835835246a83eb4408c70049b48bb070a8ff083346b00b10d650602f3").unwrap()
+        );
+    }
+}
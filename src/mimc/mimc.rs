@@ -0,0 +1,227 @@
+use super::mimc_params::MimcParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+// MiMC-p/p
+#[derive(Clone, Debug)]
+pub struct Mimc<F: PrimeField> {
+    pub(crate) params: Arc<MimcParams<F>>,
+}
+
+impl<F: PrimeField> Mimc<F> {
+    pub fn new(params: &Arc<MimcParams<F>>) -> Self {
+        Mimc {
+            params: Arc::clone(params),
+        }
+    }
+
+    // x <- (x + k + c_i)^d in each round, followed by a final key addition
+    pub fn encrypt(&self, input: &F, key: &F) -> F {
+        let mut state = *input;
+        for rc in self.params.round_constants.iter() {
+            state.add_assign(key);
+            state.add_assign(rc);
+            state = self.sbox(&state);
+        }
+        state.add_assign(key);
+        state
+    }
+
+    pub fn permutation(&self, input: &F) -> F {
+        self.encrypt(input, &F::zero())
+    }
+
+    pub fn sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    // Miyaguchi-Preneel compression E_h(m) + h + m
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut out = self.encrypt(el2, el1);
+        out.add_assign(el1);
+        out.add_assign(el2);
+        out
+    }
+
+    // Miyaguchi-Preneel chaining with an all-zero initial value
+    pub fn hash_slice(&self, input: &[F]) -> F {
+        input
+            .iter()
+            .fold(F::zero(), |state, el| self.hash(&state, el))
+    }
+}
+
+impl<F: PrimeField> MerkleTreeHash<F> for Mimc<F> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod mimc_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        mimc::mimc_instances::MIMC_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let mimc = Mimc::new(&MIMC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: Scalar = utils::random_scalar(true);
+            let mut input2: Scalar;
+            loop {
+                input2 = utils::random_scalar(true);
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = mimc.permutation(&input1);
+            let perm2 = mimc.permutation(&input1);
+            let perm3 = mimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn keyed() {
+        let mimc = Mimc::new(&MIMC_BN_PARAMS);
+        assert_eq!(MIMC_BN_PARAMS.get_rounds(), 110);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            let key1: Scalar = utils::random_scalar(false);
+            let key2: Scalar = utils::random_scalar(false);
+            assert_eq!(
+                mimc.encrypt(&input, &Scalar::zero()),
+                mimc.permutation(&input)
+            );
+            assert_ne!(mimc.encrypt(&input, &key1), mimc.encrypt(&input, &key2));
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let mimc = Mimc::new(&MIMC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = Scalar::zero();
+            for el in input.iter() {
+                state = mimc.compress(&[&state, el]);
+            }
+            assert_eq!(mimc.hash_slice(&input), state);
+
+            let mut expected = mimc.encrypt(&input[1], &input[0]);
+            expected.add_assign(&input[0]);
+            expected.add_assign(&input[1]);
+            assert_eq!(mimc.hash(&input[0], &input[1]), expected);
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let mimc = Mimc::new(&MIMC_BN_PARAMS);
+        assert_eq!(
+            mimc.permutation(&Scalar::one()),
+            from_hex("0x1c9f939cb086b161b385463b6fc901fa534b819c8c06a18c6fd418ab31e5ec2c").unwrap()
+        );
+        assert_eq!(
+            mimc.encrypt(&Scalar::one(), &utils::from_u64(2)),
+            from_hex("0x260780d2d37d2280c96f4884541a6ceb2547c3344f5bf74ff4476cd2f79b987e").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod mimc_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        mimc::mimc_instances::MIMC_BLS_PARAMS,
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let mimc = Mimc::new(&MIMC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: Scalar = utils::random_scalar(true);
+            let mut input2: Scalar;
+            loop {
+                input2 = utils::random_scalar(true);
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = mimc.permutation(&input1);
+            let perm2 = mimc.permutation(&input1);
+            let perm3 = mimc.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn keyed() {
+        let mimc = Mimc::new(&MIMC_BLS_PARAMS);
+        assert_eq!(MIMC_BLS_PARAMS.get_rounds(), 110);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            let key1: Scalar = utils::random_scalar(false);
+            let key2: Scalar = utils::random_scalar(false);
+            assert_eq!(
+                mimc.encrypt(&input, &Scalar::zero()),
+                mimc.permutation(&input)
+            );
+            assert_ne!(mimc.encrypt(&input, &key1), mimc.encrypt(&input, &key2));
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let mimc = Mimc::new(&MIMC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let mut state = Scalar::zero();
+            for el in input.iter() {
+                state = mimc.compress(&[&state, el]);
+            }
+            assert_eq!(mimc.hash_slice(&input), state);
+
+            let mut expected = mimc.encrypt(&input[1], &input[0]);
+            expected.add_assign(&input[0]);
+            expected.add_assign(&input[1]);
+            assert_eq!(mimc.hash(&input[0], &input[1]), expected);
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let mimc = Mimc::new(&MIMC_BLS_PARAMS);
+        assert_eq!(
+            mimc.permutation(&Scalar::one()),
+            from_hex("0x58fcf325ce9234bc48c31511f2ed1f41e4fdbb8d1adc237530fd6a4c3a0a148e").unwrap()
+        );
+        assert_eq!(
+            mimc.encrypt(&Scalar::one(), &utils::from_u64(2)),
+            from_hex("0x4dfa397d808404695b04eeeb540b766956a97dacab540f5a66476fee3bc239c8").unwrap()
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    mimc::{mimc_params::MimcParams, mimc_sponge::MimcSponge},
+};
+
+// 3 divides p - 1 for both fields, hence MiMC-p/p uses x^5 with
+// ceil(log_5(p)) rounds. Feistel-MiMC uses x^3 with 2 ceil(log_3(p)) rounds.
+lazy_static! {
+    // BLS12
+    pub static ref MIMC_BLS_PARAMS: Arc<MimcParams<FpBLS12>> =
+        Arc::new(MimcParams::new(5));
+    pub static ref FEISTEL_MIMC_BLS_PARAMS: Arc<MimcParams<FpBLS12>> =
+        Arc::new(MimcParams::new_feistel(3));
+
+    // BN256
+    pub static ref MIMC_BN_PARAMS: Arc<MimcParams<FpBN256>> =
+        Arc::new(MimcParams::new(5));
+    pub static ref FEISTEL_MIMC_BN_PARAMS: Arc<MimcParams<FpBN256>> =
+        Arc::new(MimcParams::new_feistel(3));
+    // circomlib's MiMCSponge with x^5, 220 rounds and keccak256 constants
+    pub static ref MIMC_SPONGE_CIRCOM_BN_PARAMS: Arc<MimcParams<FpBN256>> =
+        Arc::new(MimcParams::new_with_keccak_constants(
+            5,
+            MimcSponge::<FpBN256>::CIRCOM_ROUNDS,
+            MimcSponge::<FpBN256>::CIRCOM_SEED,
+        ));
+}
@@ -0,0 +1,111 @@
+use crate::fields::utils;
+use ff::PrimeField;
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Digest, Keccak256, Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct MimcParams<F: PrimeField> {
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) rounds: usize,
+    // c_0 = 0 as in the original design
+    pub(crate) round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> MimcParams<F> {
+    pub const INIT_SHAKE: &'static str = "MiMC";
+
+    // MiMC-p/p with ceil(log_d(p)) rounds, x^d has to be a permutation
+    pub fn new(d: usize) -> Self {
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+        Self::new_with_rounds(d, Self::log_d_p(d))
+    }
+
+    // Feistel-MiMC-2n/n with 2 ceil(log_d(p)) rounds. The Feistel network is a
+    // permutation for any d, hence x^3 can also be used if 3 | p - 1.
+    pub fn new_feistel(d: usize) -> Self {
+        assert!(d >= 2);
+        Self::new_with_rounds(d, 2 * Self::log_d_p(d))
+    }
+
+    pub fn new_with_rounds(d: usize, rounds: usize) -> Self {
+        assert!(rounds >= 1);
+        let mut shake = Self::init_shake();
+        let round_constants = Self::instantiate_rc(&mut shake, rounds);
+
+        MimcParams {
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds,
+            round_constants,
+        }
+    }
+
+    // Constants as in circomlib's mimcsponge.js: c_i = keccak256(c_(i-1)) mod p,
+    // starting from keccak256(seed) and chaining the unreduced hashes. The first
+    // and last constants are 0.
+    pub fn new_with_keccak_constants(d: usize, rounds: usize, seed: &str) -> Self {
+        assert!(d >= 2 && rounds >= 2);
+        let mut round_constants = vec![F::zero(); rounds];
+        let mut c = Keccak256::digest(seed.as_bytes());
+        for rc in round_constants.iter_mut().take(rounds - 1).skip(1) {
+            c = Keccak256::digest(c);
+            *rc = Self::from_be_bytes(&c);
+        }
+
+        MimcParams {
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds,
+            round_constants,
+        }
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> F {
+        let base = utils::from_u64::<F>(256);
+        bytes.iter().fold(F::zero(), |mut acc, b| {
+            acc.mul_assign(&base);
+            acc.add_assign(&utils::from_u64(*b as u64));
+            acc
+        })
+    }
+
+    // ceil(log_d(p)), with log_2(p) approximated from the most significant limb
+    pub fn log_d_p(d: usize) -> usize {
+        let p = F::char();
+        let limbs = p.as_ref();
+        let msl = limbs.iter().rposition(|l| *l != 0).unwrap();
+        let log2_p = (64 * msl) as f64 + f64::log2(limbs[msl] as f64);
+        f64::ceil(log2_p / f64::log2(d as f64)) as usize
+    }
+
+    fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        for i in F::char().as_ref() {
+            shake.update(&u64::to_le_bytes(*i));
+        }
+        shake.finalize_xof()
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, rounds: usize) -> Vec<F> {
+        let mut rc = Vec::with_capacity(rounds);
+        rc.push(F::zero());
+        rc.extend((1..rounds).map(|_| utils::field_element_from_shake::<F>(shake)));
+        rc
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds
+    }
+}
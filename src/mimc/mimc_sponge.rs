@@ -0,0 +1,169 @@
+use super::{feistel_mimc::FeistelMimc, mimc_params::MimcParams};
+use crate::merkle_tree::merkle_tree_fp::MerkleTreeHash;
+use ff::PrimeField;
+use std::sync::Arc;
+
+// MiMCSponge as in circomlib's mimcsponge.circom, i.e., a sponge with rate
+// and capacity of one element each over the keyed Feistel-MiMC permutation
+#[derive(Clone, Debug)]
+pub struct MimcSponge<F: PrimeField> {
+    feistel: FeistelMimc<F>,
+}
+
+impl<F: PrimeField> MimcSponge<F> {
+    pub const CIRCOM_SEED: &'static str = "mimcsponge";
+    pub const CIRCOM_ROUNDS: usize = 220;
+
+    pub fn new(params: &Arc<MimcParams<F>>) -> Self {
+        MimcSponge {
+            feistel: FeistelMimc::new(params),
+        }
+    }
+
+    // MiMCFeistel(xL, xR, k)
+    pub fn hash(&self, xl: &F, xr: &F, key: &F) -> [F; 2] {
+        self.feistel.encrypt(&[*xl, *xr], key)
+    }
+
+    // MiMCSponge(nInputs, nRounds, nOutputs) with key k
+    pub fn multi_hash(&self, inputs: &[F], key: &F, num_outputs: usize) -> Vec<F> {
+        assert!(num_outputs >= 1);
+        let mut state = [F::zero(); 2];
+        for el in inputs.iter() {
+            state[0].add_assign(el);
+            state = self.hash(&state[0], &state[1], key);
+        }
+
+        let mut outputs = Vec::with_capacity(num_outputs);
+        outputs.push(state[0]);
+        for _ in 1..num_outputs {
+            state = self.hash(&state[0], &state[1], key);
+            outputs.push(state[0]);
+        }
+        outputs
+    }
+}
+
+// Compatible with hashLeftRight of Tornado-style Merkle trees
+impl<F: PrimeField> MerkleTreeHash<F> for MimcSponge<F> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.multi_hash(&[*input[0], *input[1]], &F::zero(), 1)[0]
+    }
+}
+
+#[cfg(test)]
+mod mimc_sponge_tests_bn256 {
+    use ff::{from_hex, Field, PrimeField};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        merkle_tree::merkle_tree_fp::MerkleTree,
+        mimc::mimc_instances::MIMC_SPONGE_CIRCOM_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    // constants from circomlib
+    #[test]
+    fn circom_constants() {
+        let rc = &MIMC_SPONGE_CIRCOM_BN_PARAMS.round_constants;
+        assert_eq!(rc.len(), 220);
+        assert_eq!(rc[0], Scalar::zero());
+        assert_eq!(rc[219], Scalar::zero());
+        assert_eq!(
+            rc[1],
+            Scalar::from_str(
+                "7120861356467848435263064379192047478074060781135320967663101236819528304084"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            rc[2],
+            Scalar::from_str(
+                "5024705281721889198577876690145313457398658950011302225525409148828000436681"
+            )
+            .unwrap()
+        );
+    }
+
+    // zero values of the Merkle tree in Tornado Cash, which are computed with
+    // circomlib's MiMCSponge starting from keccak256("tornado") mod p
+    #[test]
+    fn tornado_zeros() {
+        let mimc = MimcSponge::new(&MIMC_SPONGE_CIRCOM_BN_PARAMS);
+        let zeros: [&str; 4] = [
+            "0x2fe54c60d3acabf3343a35b6eba15db4821b340f76e741e2249685ed4899af6c",
+            "0x256a6135777eee2fd26f54b8b7037a25439d5235caee224154186d2b8a52e31d",
+            "0x1151949895e82ab19924de92c40a3d6f7bcb60d92b00504b8199613683f0c200",
+            "0x20121ee811489ff8d61f09fb89e313f14959a0f28bb428a20dba6b0b068b3bdb",
+        ];
+        for level in zeros.windows(2) {
+            let zero: Scalar = from_hex(level[0]).unwrap();
+            assert_eq!(mimc.compress(&[&zero, &zero]), from_hex(level[1]).unwrap());
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let mimc = MimcSponge::new(&MIMC_SPONGE_CIRCOM_BN_PARAMS);
+        let one = utils::from_u64::<Scalar>(1);
+        let two = utils::from_u64::<Scalar>(2);
+        let three = utils::from_u64::<Scalar>(3);
+
+        let perm = mimc.hash(&one, &two, &three);
+        assert_eq!(
+            perm[0],
+            from_hex("0x28c6f78ee3ed6b336280d3e522b03efc49eeb5a2a3af1075ccf6f64e5d867e53").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x05d9ff7555e18007f7809e5977e6dd41ff93ca7c8d11249d51eaeb0b4f727d37").unwrap()
+        );
+
+        let outputs = mimc.multi_hash(&[one, two, three], &Scalar::zero(), 3);
+        assert_eq!(
+            outputs[0],
+            from_hex("0x1d824407e6b579a4052dc76880c041ae440e4dafd5c7f6ea262336c417de4692").unwrap()
+        );
+        assert_eq!(
+            outputs[1],
+            from_hex("0x2fd2eb3632e9b58e8ea699ea7cd8768d64b1c09b07e9585a25183bdb078e1af0").unwrap()
+        );
+        assert_eq!(
+            outputs[2],
+            from_hex("0x2e26054c39ee30b422cb30a801ec459bcddadca9756d50beccd4c342ccbc688e").unwrap()
+        );
+    }
+
+    #[test]
+    fn multiple_outputs() {
+        let mimc = MimcSponge::new(&MIMC_SPONGE_CIRCOM_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let inputs: Vec<Scalar> = (0..3).map(|_| utils::random_scalar(true)).collect();
+            let key: Scalar = utils::random_scalar(true);
+            let outputs = mimc.multi_hash(&inputs, &key, 4);
+            assert_eq!(outputs.len(), 4);
+            assert_eq!(mimc.multi_hash(&inputs, &key, 1)[0], outputs[0]);
+            assert_ne!(mimc.multi_hash(&inputs, &Scalar::zero(), 1)[0], outputs[0]);
+        }
+    }
+
+    #[test]
+    fn merkle_tree() {
+        let mimc = MimcSponge::new(&MIMC_SPONGE_CIRCOM_BN_PARAMS);
+        let mut tree = MerkleTree::new(mimc.clone());
+        for _ in 0..TESTRUNS {
+            let leaves: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let left = mimc.multi_hash(&leaves[..2], &Scalar::zero(), 1)[0];
+            let right = mimc.multi_hash(&leaves[2..], &Scalar::zero(), 1)[0];
+            assert_eq!(
+                tree.accumulate(&leaves),
+                mimc.multi_hash(&[left, right], &Scalar::zero(), 1)[0]
+            );
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod feistel_mimc;
+#[allow(clippy::module_inception)]
+pub mod mimc;
+pub mod mimc_instances;
+pub mod mimc_params;
+pub mod mimc_sponge;
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod monolith;
+pub mod monolith_instances;
+pub mod monolith_params;
@@ -0,0 +1,492 @@
+use super::monolith_params::MonolithParams;
+use crate::merkle_tree::merkle_tree_fp::MerkleTreeHash;
+use ff::PrimeField;
+use std::sync::Arc;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "constant-time")] {
+        #[inline(always)]
+        fn lookup(table: &[u16], index: u16) -> u16 {
+            crate::fields::utils::ct_lookup(table, index)
+        }
+    } else {
+        #[inline(always)]
+        fn lookup(table: &[u16], index: u16) -> u16 {
+            // safe because the tables have 2^bits entries for limbs of size bits
+            unsafe { *table.get_unchecked(index as usize) }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monolith<F: PrimeField, const T: usize = 8> {
+    pub(crate) params: Arc<MonolithParams<F>>,
+}
+
+impl<F: PrimeField> Monolith<F> {
+    pub fn new(params: &Arc<MonolithParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Monolith<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Monolith::<F, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16)
+    pub fn new_wide(params: &Arc<MonolithParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Monolith {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    // Concrete, followed by rounds of Bars, Bricks, Concrete and the addition
+    // of round constants. No constants are added in the last round.
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        self.concrete(&mut state);
+        for rc in self.params.round_constants.iter() {
+            self.bars(&mut state);
+            self.round(&mut state, rc);
+        }
+        self.bars(&mut state);
+        self.bricks(&mut state);
+        self.concrete(&mut state);
+        state
+    }
+
+    // Same as permutation, but the S-boxes in Bars are table lookups
+    pub fn permutation_lookup(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        self.concrete(&mut state);
+        for rc in self.params.round_constants.iter() {
+            self.bars_lookup(&mut state);
+            self.round(&mut state, rc);
+        }
+        self.bars_lookup(&mut state);
+        self.bricks(&mut state);
+        self.concrete(&mut state);
+        state
+    }
+
+    fn round(&self, state: &mut [F; T], rc: &[F]) {
+        self.bricks(state);
+        self.concrete(state);
+        for (el, c) in state.iter_mut().zip(rc.iter()) {
+            el.add_assign(c);
+        }
+    }
+
+    // Applies the chi-like S-boxes to the limbs of the first elements
+    pub fn bars(&self, state: &mut [F; T]) {
+        for el in state.iter_mut().take(self.params.bars) {
+            *el = self.bar(el, |limb, bits, _| MonolithParams::<F>::sbox(limb, bits));
+        }
+    }
+
+    pub fn bars_lookup(&self, state: &mut [F; T]) {
+        for el in state.iter_mut().take(self.params.bars) {
+            *el = self.bar(el, |limb, _, table| lookup(table, limb));
+        }
+    }
+
+    fn bar<S: Fn(u16, u32, &[u16]) -> u16>(&self, el: &F, sbox: S) -> F {
+        let mut repr = el.into_repr();
+        let mut val = repr.as_ref()[0];
+        let mut res = 0u64;
+        let mut shift = 0;
+        for (bits, table) in self.params.limbs.iter().zip(self.params.lookup.iter()) {
+            let limb = (val & ((1 << bits) - 1)) as u16;
+            res |= (sbox(limb, *bits, table) as u64) << shift;
+            val >>= bits;
+            shift += bits;
+        }
+        repr.as_mut()[0] = res;
+        // the S-boxes fix the all-zero and all-one limbs, hence res < p
+        F::from_repr(repr).unwrap()
+    }
+
+    // Type-3 Feistel network with the square map: y_i = x_i + x_{i-1}^2
+    pub fn bricks(&self, state: &mut [F; T]) {
+        for i in (1..T).rev() {
+            let mut sq = state[i - 1];
+            sq.square();
+            state[i].add_assign(&sq);
+        }
+    }
+
+    // Multiplication with the MDS matrix
+    pub fn concrete(&self, state: &mut [F; T]) {
+        let input = state.to_owned();
+        for (el, row) in state.iter_mut().zip(self.params.mds.iter()) {
+            *el = F::zero();
+            for (a, b) in row.iter().zip(input.iter()) {
+                let mut tmp = *a;
+                tmp.mul_assign(b);
+                el.add_assign(&tmp);
+            }
+        }
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Monolith<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod monolith_tests_goldilocks {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{goldilocks::FpGoldilocks, utils},
+        monolith::monolith_instances::{
+            MONOLITH_GOLDILOCKS_PARAMS_T12, MONOLITH_GOLDILOCKS_PARAMS_T8,
+        },
+    };
+
+    type Scalar = FpGoldilocks;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        for el in state.iter_mut() {
+            *el = utils::random_scalar(true);
+        }
+        state
+    }
+
+    fn kat<const T: usize>(monolith: &Monolith<Scalar, T>) -> [Scalar; T] {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        monolith.permutation(&input)
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<8>();
+            let mut input2;
+            loop {
+                input2 = random_state::<8>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = monolith.permutation(&input1);
+            let perm2 = monolith.permutation(&input1);
+            let perm3 = monolith.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let monolith = Monolith::<Scalar, 12>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T12);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<12>();
+            let mut bars = input;
+            monolith.bars(&mut bars);
+            let mut bars_lookup = input;
+            monolith.bars_lookup(&mut bars_lookup);
+            assert_eq!(bars, bars_lookup);
+            assert_eq!(bars[monolith.params.bars..], input[monolith.params.bars..]);
+            assert_eq!(
+                monolith.permutation(&input),
+                monolith.permutation_lookup(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn bars() {
+        for bits in [7, 8] {
+            let mut image: Vec<u16> = (0..1u16 << bits)
+                .map(|x| MonolithParams::<Scalar>::sbox(x, bits))
+                .collect();
+            assert_eq!(image[0], 0);
+            assert_eq!(image[(1 << bits) - 1], (1 << bits) - 1);
+            image.sort_unstable();
+            assert!(image.iter().enumerate().all(|(i, y)| i as u16 == *y));
+        }
+
+        let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+        let mut minus_one = Scalar::one();
+        minus_one.negate();
+        let mut state = [Scalar::zero(); 8];
+        state[1] = minus_one;
+        // p - 1 only consists of all-zero and all-one limbs
+        monolith.bars(&mut state);
+        assert_eq!(state[0], Scalar::zero());
+        assert_eq!(state[1], minus_one);
+
+        // the outputs of Bars have to be valid field elements
+        for _ in 0..TESTRUNS {
+            let mut state = random_state::<8>();
+            monolith.bars(&mut state);
+            for el in state.iter() {
+                assert!(el.into_repr() < Scalar::char());
+                assert_eq!(Scalar::from_repr(el.into_repr()).unwrap(), *el);
+            }
+        }
+    }
+
+    #[test]
+    fn bricks() {
+        let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<8>();
+            let mut output = input;
+            monolith.bricks(&mut output);
+            assert_eq!(output[0], input[0]);
+            for i in 1..8 {
+                let mut expected = input[i - 1];
+                expected.square();
+                expected.add_assign(&input[i]);
+                assert_eq!(output[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 8];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(monolith.hash_n(&input), monolith.permutation(&state)[0]);
+            assert_eq!(
+                monolith.compress(&[&input[0], &input[1]]),
+                monolith.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let monolith = Monolith::<Scalar, 8>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T8);
+        let perm = kat(&monolith);
+        assert_eq!(perm[0], from_hex("0x32be4af2d3128873").unwrap());
+        assert_eq!(perm[7], from_hex("0x7f23d5656dda898f").unwrap());
+
+        // test vector of the reference implementation
+        let monolith = Monolith::<Scalar, 12>::new_wide(&MONOLITH_GOLDILOCKS_PARAMS_T12);
+        let expected = [
+            "0x516dd661e959f541",
+            "0x082c137169707901",
+            "0x53dff3fd9f0a5beb",
+            "0x0b2ebaa261590650",
+            "0x89aadb57e2969cb6",
+            "0x5d3d6905970259bd",
+            "0x6e5ac1a4c0cfa0fe",
+            "0xd674b7736abfc5ce",
+            "0x0d8697e1cd9a235f",
+            "0x85fc4017c247136e",
+            "0x572bafd76e511424",
+            "0xbec1638e28eae57f",
+        ];
+        for (el, exp) in kat(&monolith).iter().zip(expected.iter()) {
+            assert_eq!(*el, from_hex(exp).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod monolith_tests_mersenne31 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{mersenne31::FpMersenne31, utils},
+        monolith::monolith_instances::{
+            MONOLITH_MERSENNE_PARAMS_T16, MONOLITH_MERSENNE_PARAMS_T24,
+        },
+    };
+
+    type Scalar = FpMersenne31;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        for el in state.iter_mut() {
+            *el = utils::random_scalar(true);
+        }
+        state
+    }
+
+    fn kat<const T: usize>(monolith: &Monolith<Scalar, T>) -> [Scalar; T] {
+        let mut input = [Scalar::zero(); T];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        monolith.permutation(&input)
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<16>();
+            let mut input2;
+            loop {
+                input2 = random_state::<16>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = monolith.permutation(&input1);
+            let perm2 = monolith.permutation(&input1);
+            let perm3 = monolith.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let monolith = Monolith::<Scalar, 24>::new_wide(&MONOLITH_MERSENNE_PARAMS_T24);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<24>();
+            let mut bars = input;
+            monolith.bars(&mut bars);
+            let mut bars_lookup = input;
+            monolith.bars_lookup(&mut bars_lookup);
+            assert_eq!(bars, bars_lookup);
+            assert_eq!(bars[monolith.params.bars..], input[monolith.params.bars..]);
+            assert_eq!(
+                monolith.permutation(&input),
+                monolith.permutation_lookup(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn bars() {
+        for bits in [7, 8] {
+            let mut image: Vec<u16> = (0..1u16 << bits)
+                .map(|x| MonolithParams::<Scalar>::sbox(x, bits))
+                .collect();
+            assert_eq!(image[0], 0);
+            assert_eq!(image[(1 << bits) - 1], (1 << bits) - 1);
+            image.sort_unstable();
+            assert!(image.iter().enumerate().all(|(i, y)| i as u16 == *y));
+        }
+
+        let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+        let mut minus_one = Scalar::one();
+        minus_one.negate();
+        let mut state = [Scalar::zero(); 16];
+        state[1] = minus_one;
+        // the lowest limb 0xfe of p - 1 is not fixed, but no output reaches p,
+        // which is the only value with all-one limbs
+        monolith.bars(&mut state);
+        assert_eq!(state[0], Scalar::zero());
+        assert_eq!(state[1], from_hex("0x7ffffff9").unwrap());
+
+        // the outputs of Bars have to be valid field elements
+        for _ in 0..TESTRUNS {
+            let mut state = random_state::<16>();
+            monolith.bars(&mut state);
+            for el in state.iter() {
+                assert!(el.into_repr() < Scalar::char());
+                assert_eq!(Scalar::from_repr(el.into_repr()).unwrap(), *el);
+            }
+        }
+    }
+
+    #[test]
+    fn bricks() {
+        let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<16>();
+            let mut output = input;
+            monolith.bricks(&mut output);
+            assert_eq!(output[0], input[0]);
+            for i in 1..16 {
+                let mut expected = input[i - 1];
+                expected.square();
+                expected.add_assign(&input[i]);
+                assert_eq!(output[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 16];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(monolith.hash_n(&input), monolith.permutation(&state)[0]);
+            assert_eq!(
+                monolith.compress(&[&input[0], &input[1]]),
+                monolith.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        // test vector of the reference implementation
+        let monolith = Monolith::<Scalar, 16>::new_wide(&MONOLITH_MERSENNE_PARAMS_T16);
+        let expected = [
+            "0x244efdff",
+            "0x114aaee6",
+            "0x714b1766",
+            "0x67658973",
+            "0x7a3fb293",
+            "0x6242ed1c",
+            "0x4df11d40",
+            "0x73b3d204",
+            "0x65c07a69",
+            "0x5814214f",
+            "0x45bf3c99",
+            "0x059748f7",
+            "0x2de36d34",
+            "0x16626a57",
+            "0x7698d0ec",
+            "0x506d1343",
+        ];
+        for (el, exp) in kat(&monolith).iter().zip(expected.iter()) {
+            assert_eq!(*el, from_hex(exp).unwrap());
+        }
+
+        let monolith = Monolith::<Scalar, 24>::new_wide(&MONOLITH_MERSENNE_PARAMS_T24);
+        let perm = kat(&monolith);
+        assert_eq!(perm[0], from_hex("0x4cf6b3aa").unwrap());
+        assert_eq!(perm[23], from_hex("0x29e48cf9").unwrap());
+    }
+}
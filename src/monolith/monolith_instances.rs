@@ -0,0 +1,48 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{goldilocks::FpGoldilocks, mersenne31::FpMersenne31},
+    monolith::monolith_params::MonolithParams,
+};
+
+// Goldilocks: Bars on 4 elements with 8 bit limbs and circulant MDS matrices
+// with small entries. Mersenne31: Bars on 8 elements with limbs of 8, 8, 8 and
+// 7 bits, the circulant MDS matrix with small entries of the reference
+// implementation for t = 16 and its SHAKE128 derived Cauchy matrix for t = 24.
+lazy_static! {
+    // Goldilocks
+    pub static ref MONOLITH_GOLDILOCKS_PARAMS_T8: Arc<MonolithParams<FpGoldilocks>> =
+        Arc::new(MonolithParams::new(
+            8,
+            4,
+            &[8; 8],
+            &MonolithParams::circulant(&[23, 8, 13, 10, 7, 6, 21, 8]),
+        ));
+    pub static ref MONOLITH_GOLDILOCKS_PARAMS_T12: Arc<MonolithParams<FpGoldilocks>> =
+        Arc::new(MonolithParams::new(
+            12,
+            4,
+            &[8; 8],
+            &MonolithParams::circulant(&[7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8]),
+        ));
+
+    // Mersenne31
+    pub static ref MONOLITH_MERSENNE_PARAMS_T16: Arc<MonolithParams<FpMersenne31>> =
+        Arc::new(MonolithParams::new(
+            16,
+            8,
+            &[8, 8, 8, 7],
+            &MonolithParams::circulant(&[
+                61402, 17845, 26798, 59689, 12021, 40901, 41351, 27521, 56951, 12034, 53865,
+                43244, 7454, 33823, 28750, 1108,
+            ]),
+        ));
+    pub static ref MONOLITH_MERSENNE_PARAMS_T24: Arc<MonolithParams<FpMersenne31>> =
+        Arc::new(MonolithParams::new(
+            24,
+            8,
+            &[8, 8, 8, 7],
+            &MonolithParams::cauchy_mds(24),
+        ));
+}
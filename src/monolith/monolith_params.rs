@@ -0,0 +1,174 @@
+use crate::fields::utils;
+use ff::PrimeField;
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct MonolithParams<F: PrimeField> {
+    pub(crate) t: usize,
+    // number of elements the Bars layer is applied to
+    pub(crate) bars: usize,
+    pub(crate) rounds: usize,
+    // bit sizes of the limbs in Bars, least significant limb first
+    pub(crate) limbs: Vec<u32>,
+    pub(crate) mds: Vec<Vec<F>>,
+    // no constants are added in the last round
+    pub(crate) round_constants: Vec<Vec<F>>,
+    // S-box of each limb as lookup table
+    pub(crate) lookup: Vec<Vec<u16>>,
+}
+
+impl<F: PrimeField> MonolithParams<F> {
+    pub const INIT_SHAKE: &'static str = "Monolith";
+    pub const ROUNDS: usize = 6;
+
+    // The limbs have to cover p, and Bars is only a permutation if no value >= p
+    // is reached. The S-boxes fix the all-zero and all-one limbs, hence this
+    // holds for Goldilocks with 8 bit limbs, where p - 1 is fixed and the values
+    // >= p keep their all-one upper half, and for 2^31 - 1 with limbs of 8, 8, 8
+    // and 7 bits, where p is the only all-one value
+    pub fn new(t: usize, bars: usize, limbs: &[u32], mds: &[Vec<F>]) -> Self {
+        assert!(bars >= 1 && bars <= t);
+        assert!(limbs.iter().all(|l| *l == 7 || *l == 8));
+        assert!(limbs.iter().sum::<u32>() == F::NUM_BITS && F::NUM_BITS <= 64);
+        assert!(mds.len() == t && mds.iter().all(|row| row.len() == t));
+
+        let limb_bytes: Vec<u8> = limbs.iter().map(|l| *l as u8).collect();
+        let mut shake = Self::init_shake(t, &limb_bytes);
+        let round_constants = Self::instantiate_rc(&mut shake, t, Self::ROUNDS - 1);
+        let lookup = limbs
+            .iter()
+            .map(|bits| (0..1u16 << bits).map(|x| Self::sbox(x, *bits)).collect())
+            .collect();
+
+        MonolithParams {
+            t,
+            bars,
+            rounds: Self::ROUNDS,
+            limbs: limbs.to_owned(),
+            mds: mds.to_owned(),
+            round_constants,
+            lookup,
+        }
+    }
+
+    // chi-like S-box followed by a rotation by one bit:
+    // 8 bit: (y ^ (~(y <<< 1) & (y <<< 2) & (y <<< 3))) <<< 1
+    // 7 bit: (y ^ (~(y <<< 1) & (y <<< 2))) <<< 1
+    pub fn sbox(y: u16, bits: u32) -> u16 {
+        let mask = (1u16 << bits) - 1;
+        let rotl = |x: u16, r: u32| ((x << r) | (x >> (bits - r))) & mask;
+
+        let mut tmp = !rotl(y, 1) & rotl(y, 2);
+        if bits == 8 {
+            tmp &= rotl(y, 3);
+        }
+        rotl(y ^ tmp, 1)
+    }
+
+    // Elements are sampled from 4 byte words for primes of up to 32 bits and
+    // from 8 byte words otherwise, as in the reference implementation
+    fn element_bytes() -> usize {
+        if F::NUM_BITS <= 32 {
+            4
+        } else {
+            8
+        }
+    }
+
+    // Seeded as in the reference implementation with the width, the total
+    // number of rounds, p and the bit sizes of the limbs
+    fn init_shake(t: usize, limbs: &[u8]) -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let bytes = Self::element_bytes();
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        shake.update(&[t as u8, Self::ROUNDS as u8]);
+        shake.update(&u64::to_le_bytes(F::char().as_ref()[0])[..bytes]);
+        shake.update(limbs);
+        shake.finalize_xof()
+    }
+
+    // rejection sampling of little endian words below p
+    fn field_element_from_shake(shake: &mut dyn XofReader) -> F {
+        let bytes = Self::element_bytes();
+        let mut buf = [0u8; 8];
+        loop {
+            shake.read(&mut buf[..bytes]);
+            if let Ok(el) = F::from_repr(F::Repr::from(u64::from_le_bytes(buf))) {
+                return el;
+            }
+        }
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, t: usize, rounds: usize) -> Vec<Vec<F>> {
+        (0..rounds)
+            .map(|_| {
+                (0..t)
+                    .map(|_| Self::field_element_from_shake(shake))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn circulant(first_row: &[u64]) -> Vec<Vec<F>> {
+        let t = first_row.len();
+        (0..t)
+            .map(|i| {
+                (0..t)
+                    .map(|j| utils::from_u64::<F>(first_row[(t + j - i) % t]))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Cauchy matrix M[i][j] = 1 / (x_i + y_j) for 31 bit primes, derived as in
+    // the reference implementation for widths without a small circulant matrix:
+    // The y_j are random 29 bit words whose lower 22 bits x_j are distinct. The
+    // SHAKE128 instance is seeded as for the round constants, but with the limb
+    // sizes 16 and 15 followed by "MDS".
+    pub fn cauchy_mds(t: usize) -> Vec<Vec<F>> {
+        assert!(F::NUM_BITS == 31);
+        let x_mask = (1u32 << (F::NUM_BITS - 9)) - 1;
+        let y_mask = ((1u32 << F::NUM_BITS) - 1) >> 2;
+
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        shake.update(&[t as u8, Self::ROUNDS as u8]);
+        shake.update(&u32::to_le_bytes(F::char().as_ref()[0] as u32));
+        shake.update(&[16, 15]);
+        shake.update(b"MDS");
+        let mut shake = shake.finalize_xof();
+
+        let mut y: Vec<u32> = Vec::with_capacity(t);
+        while y.len() < t {
+            let mut buf = [0u8; 4];
+            shake.read(&mut buf);
+            let y_j = u32::from_le_bytes(buf) & y_mask;
+            if y.iter().all(|y_k| y_k & x_mask != y_j & x_mask) {
+                y.push(y_j);
+            }
+        }
+
+        y.iter()
+            .map(|x_i| {
+                (0..t)
+                    .map(|j| {
+                        utils::from_u64::<F>(((x_i & x_mask) + y[j]) as u64)
+                            .inverse()
+                            .unwrap()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds
+    }
+}
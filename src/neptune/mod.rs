@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod neptune;
+pub mod neptune_instances;
+pub mod neptune_params;
@@ -0,0 +1,379 @@
+use super::neptune_params::NeptuneParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Neptune<F: PrimeField, const T: usize = 4> {
+    pub(crate) params: Arc<NeptuneParams<F>>,
+}
+
+impl<F: PrimeField> Neptune<F> {
+    pub fn new(params: &Arc<NeptuneParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Neptune<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Neptune::<F, 8>::new_wide(&params) for 7-ary Merkle trees
+    pub fn new_wide(params: &Arc<NeptuneParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Neptune {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+
+        // linear layer at the beginning
+        self.matmul_external(&mut state);
+
+        for rc in self.params.external_rc[..self.params.rounds_f_beginning].iter() {
+            self.external_round(&mut state, rc);
+        }
+
+        for rc in self.params.internal_rc.iter() {
+            state[0].add_assign(rc);
+            state[0] = self.internal_sbox(&state[0]);
+            self.matmul_internal(&mut state);
+        }
+
+        for rc in self.params.external_rc[self.params.rounds_f_beginning..].iter() {
+            self.external_round(&mut state, rc);
+        }
+        state
+    }
+
+    fn external_round(&self, state: &mut [F; T], rc: &[F]) {
+        for (el, c) in state.iter_mut().zip(rc.iter()) {
+            el.add_assign(c);
+        }
+        self.external_sbox(state);
+        self.matmul_external(state);
+    }
+
+    // S'(x_0, x_1) applied to all pairs (x_2i, x_2i+1)
+    pub fn external_sbox(&self, state: &mut [F; T]) {
+        for pair in state.chunks_exact_mut(2) {
+            let (y0, y1) = Self::external_sbox_prime(&pair[0], &pair[1]);
+            pair[0] = y0;
+            pair[1] = y1;
+        }
+    }
+
+    // S'(x_0, x_1) = (2 x_0 + x_1 + 3 (x_0 - x_1)^2, x_0 + 3 x_1 + 4 (x_0 - x_1)^2)
+    // is a quadratic permutation of F_p^2, since 4 y_0 - 3 y_1 = 5 (x_0 - x_1)
+    fn external_sbox_prime(x0: &F, x1: &F) -> (F, F) {
+        let mut diff = *x0;
+        diff.sub_assign(x1);
+        diff.square();
+
+        let mut sum = *x0;
+        sum.add_assign(x1);
+
+        // 2 x_0 + x_1 + 3 (x_0 - x_1)^2
+        let mut y0 = sum;
+        y0.add_assign(x0);
+        let mut tmp = diff;
+        tmp.double();
+        tmp.add_assign(&diff);
+        y0.add_assign(&tmp);
+
+        // x_0 + 3 x_1 + 4 (x_0 - x_1)^2
+        let mut y1 = sum;
+        let mut x1_2 = *x1;
+        x1_2.double();
+        y1.add_assign(&x1_2);
+        tmp.add_assign(&diff);
+        y1.add_assign(&tmp);
+
+        (y0, y1)
+    }
+
+    pub fn internal_sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    // M_E acts independently on the even and odd elements, with 1 + J on the
+    // even and 2 + J on the odd ones, where J is the all-ones matrix
+    pub fn matmul_external(&self, state: &mut [F; T]) {
+        let mut sum_even = F::zero();
+        let mut sum_odd = F::zero();
+        for pair in state.chunks_exact(2) {
+            sum_even.add_assign(&pair[0]);
+            sum_odd.add_assign(&pair[1]);
+        }
+        for pair in state.chunks_exact_mut(2) {
+            pair[0].add_assign(&sum_even);
+            pair[1].double();
+            pair[1].add_assign(&sum_odd);
+        }
+    }
+
+    // M_I = 1 + diag(mat_internal_diag_m_1), i.e., x_i' = sum(x) + diag_i * x_i
+    pub fn matmul_internal(&self, state: &mut [F; T]) {
+        let mut sum = state[0];
+        state.iter().skip(1).for_each(|el| sum.add_assign(el));
+        for (el, diag) in state
+            .iter_mut()
+            .zip(self.params.mat_internal_diag_m_1.iter())
+        {
+            el.mul_assign(diag);
+            el.add_assign(&sum);
+        }
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Neptune<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod neptune_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        neptune::neptune_instances::{NEPTUNE_BN_PARAMS, NEPTUNE_BN_PARAMS_T8},
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let neptune = Neptune::new(&NEPTUNE_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 4] = [(); 4].map(|_| utils::random_scalar(true));
+
+            let mut input2: [Scalar; 4];
+            loop {
+                input2 = [(); 4].map(|_| utils::random_scalar(true));
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = neptune.permutation(&input1);
+            let perm2 = neptune.permutation(&input1);
+            let perm3 = neptune.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn external_sbox_inverse() {
+        let neptune = Neptune::new(&NEPTUNE_BN_PARAMS);
+        let five_inv = utils::from_u64::<Scalar>(5).inverse().unwrap();
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 4] = [(); 4].map(|_| utils::random_scalar(true));
+            let mut state = input;
+            neptune.external_sbox(&mut state);
+
+            for (x, y) in input.chunks_exact(2).zip(state.chunks_exact(2)) {
+                // x_0 - x_1 = (4 y_0 - 3 y_1) / 5
+                let mut diff = y[0];
+                diff.mul_assign(&utils::from_u64(4));
+                let mut tmp = y[1];
+                tmp.mul_assign(&utils::from_u64(3));
+                diff.sub_assign(&tmp);
+                diff.mul_assign(&five_inv);
+                let mut expected = x[0];
+                expected.sub_assign(&x[1]);
+                assert_eq!(diff, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let neptune = Neptune::<Scalar, 8>::new_wide(&NEPTUNE_BN_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..7).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 8];
+            state[..7].copy_from_slice(&input);
+            assert_eq!(neptune.hash_n(&input), neptune.permutation(&state)[0]);
+            assert_eq!(
+                neptune.compress(&[&input[0], &input[1]]),
+                neptune.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let neptune = Neptune::new(&NEPTUNE_BN_PARAMS);
+        let mut input = [Scalar::zero(); 4];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = neptune.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x27ad9f75f6dacee3676b1d581baf91857f8a21628eb10b6db7a772875ce68d07").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x1d4b1a568c73b3840292dc162afe4d175e46febc161589903d4f7996dc8a3676").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x1660c7afcec8b75ed69fbc4997698e4e24679cb90b80861f6d80613c127c497c").unwrap()
+        );
+        assert_eq!(
+            perm[3],
+            from_hex("0x001e90cc9f5fbff629d9723778f50d05fbb485b479c3edbf6d84ee9fe621292d").unwrap()
+        );
+
+        let neptune = Neptune::<Scalar, 8>::new_wide(&NEPTUNE_BN_PARAMS_T8);
+        let mut input = [Scalar::zero(); 8];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            neptune.permutation(&input)[0],
+            from_hex("0x1eef02a3c127b7d0b7d145111c949e3e07ec6953d5b4ec13b1e2823505888dc8").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod neptune_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        neptune::neptune_instances::{NEPTUNE_BLS_PARAMS, NEPTUNE_BLS_PARAMS_T8},
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let neptune = Neptune::new(&NEPTUNE_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 4] = [(); 4].map(|_| utils::random_scalar(true));
+
+            let mut input2: [Scalar; 4];
+            loop {
+                input2 = [(); 4].map(|_| utils::random_scalar(true));
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = neptune.permutation(&input1);
+            let perm2 = neptune.permutation(&input1);
+            let perm3 = neptune.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn external_sbox_inverse() {
+        let neptune = Neptune::new(&NEPTUNE_BLS_PARAMS);
+        let five_inv = utils::from_u64::<Scalar>(5).inverse().unwrap();
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 4] = [(); 4].map(|_| utils::random_scalar(true));
+            let mut state = input;
+            neptune.external_sbox(&mut state);
+
+            for (x, y) in input.chunks_exact(2).zip(state.chunks_exact(2)) {
+                // x_0 - x_1 = (4 y_0 - 3 y_1) / 5
+                let mut diff = y[0];
+                diff.mul_assign(&utils::from_u64(4));
+                let mut tmp = y[1];
+                tmp.mul_assign(&utils::from_u64(3));
+                diff.sub_assign(&tmp);
+                diff.mul_assign(&five_inv);
+                let mut expected = x[0];
+                expected.sub_assign(&x[1]);
+                assert_eq!(diff, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let neptune = Neptune::<Scalar, 8>::new_wide(&NEPTUNE_BLS_PARAMS_T8);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..7).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 8];
+            state[..7].copy_from_slice(&input);
+            assert_eq!(neptune.hash_n(&input), neptune.permutation(&state)[0]);
+            assert_eq!(
+                neptune.compress(&[&input[0], &input[1]]),
+                neptune.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let neptune = Neptune::new(&NEPTUNE_BLS_PARAMS);
+        let mut input = [Scalar::zero(); 4];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = neptune.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x06a5b1d611611800906d4b8953ff913d4a4c4031c90d24598cdc0bf22fdb8fa4").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x27d89a87b4304dfd06e506c6bf8ec94c88895d11941050d2085f4eee6256d7bc").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x4cf4c9630b628bd0af12132c2276d5f6484face416d6fbe28bd551231916ed3b").unwrap()
+        );
+        assert_eq!(
+            perm[3],
+            from_hex("0x72c66e0ab6319f9491ae14e13bc62e807463293947bc239b421e67acd74d0838").unwrap()
+        );
+
+        let neptune = Neptune::<Scalar, 8>::new_wide(&NEPTUNE_BLS_PARAMS_T8);
+        let mut input = [Scalar::zero(); 8];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            neptune.permutation(&input)[0],
+            from_hex("0x0cbaa947c305611a2ba7e67a5765ef5259217c5a04ffba1996d8c260d0417a86").unwrap()
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    neptune::neptune_params::NeptuneParams,
+};
+
+// x^5 in the internal rounds, 6 external rounds and the number of internal
+// rounds for 128 bit security
+lazy_static! {
+    // BLS12
+    pub static ref NEPTUNE_BLS_PARAMS: Arc<NeptuneParams<FpBLS12>> =
+        Arc::new(NeptuneParams::new(4, 5, 6, 68));
+    pub static ref NEPTUNE_BLS_PARAMS_T8: Arc<NeptuneParams<FpBLS12>> =
+        Arc::new(NeptuneParams::new(8, 5, 6, 72));
+
+    // BN256
+    pub static ref NEPTUNE_BN_PARAMS: Arc<NeptuneParams<FpBN256>> =
+        Arc::new(NeptuneParams::new(4, 5, 6, 68));
+    pub static ref NEPTUNE_BN_PARAMS_T8: Arc<NeptuneParams<FpBN256>> =
+        Arc::new(NeptuneParams::new(8, 5, 6, 72));
+}
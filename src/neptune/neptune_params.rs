@@ -0,0 +1,107 @@
+use crate::fields::utils;
+use ff::PrimeField;
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct NeptuneParams<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) rounds_f_beginning: usize,
+    pub(crate) rounds_p: usize,
+    pub(crate) rounds_f_end: usize,
+    // M_I = 1 + diag(mat_internal_diag_m_1)
+    pub(crate) mat_internal_diag_m_1: Vec<F>,
+    pub(crate) external_rc: Vec<Vec<F>>,
+    pub(crate) internal_rc: Vec<F>,
+}
+
+impl<F: PrimeField> NeptuneParams<F> {
+    pub const INIT_SHAKE: &'static str = "Neptune";
+
+    // rounds_f external rounds, split evenly, and rounds_p internal rounds
+    pub fn new(t: usize, d: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        assert!(t >= 4 && t.is_multiple_of(2), "unsupported width {}", t);
+        assert!(rounds_f.is_multiple_of(2) && rounds_f >= 2 && rounds_p >= 1);
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+
+        let mut shake = Self::init_shake();
+        let external_rc: Vec<Vec<F>> = (0..rounds_f)
+            .map(|_| {
+                (0..t)
+                    .map(|_| utils::field_element_from_shake(&mut shake))
+                    .collect()
+            })
+            .collect();
+        let internal_rc: Vec<F> = (0..rounds_p)
+            .map(|_| utils::field_element_from_shake(&mut shake))
+            .collect();
+        let mat_internal_diag_m_1 = Self::instantiate_internal_diag(&mut shake, t);
+
+        let rounds_f_beginning = rounds_f / 2;
+        NeptuneParams {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds_f_beginning,
+            rounds_p,
+            rounds_f_end: rounds_f - rounds_f_beginning,
+            mat_internal_diag_m_1,
+            external_rc,
+            internal_rc,
+        }
+    }
+
+    fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        for i in F::char().as_ref() {
+            shake.update(&u64::to_le_bytes(*i));
+        }
+        shake.finalize_xof()
+    }
+
+    // By the matrix determinant lemma, D + 1 1^T with D = diag(d_i) is
+    // invertible iff all d_i != 0 and 1 + sum(1 / d_i) != 0
+    fn instantiate_internal_diag(shake: &mut dyn XofReader, t: usize) -> Vec<F> {
+        loop {
+            let diag: Vec<F> = (0..t)
+                .map(|_| utils::field_element_from_shake_without_0(shake))
+                .collect();
+            let mut det = F::one();
+            for el in diag.iter() {
+                det.add_assign(&el.inverse().unwrap());
+            }
+            if !det.is_zero() {
+                return diag;
+            }
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_p + self.rounds_f_end
+    }
+
+    pub fn get_rounds_f(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_f_end
+    }
+
+    pub fn get_rounds_p(&self) -> usize {
+        self.rounds_p
+    }
+}
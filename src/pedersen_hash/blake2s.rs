@@ -0,0 +1,130 @@
+// BLAKE2s-256 (RFC 7693) with an 8 byte personalization, as used by the group
+// hash of Zcash Sapling. Only unkeyed hashing with a fixed output length of
+// 32 bytes is supported.
+
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLOCK_BYTES: usize = 64;
+const OUT_BYTES: usize = 32;
+
+#[inline(always)]
+fn g(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
+
+fn compress(h: &mut [u32; 8], block: &[u8; BLOCK_BYTES], counter: u64, last: bool) {
+    let mut m = [0u32; 16];
+    for (w, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= counter as u32;
+    v[13] ^= (counter >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for s in SIGMA.iter() {
+        g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for (i, el) in h.iter_mut().enumerate() {
+        *el ^= v[i] ^ v[i + 8];
+    }
+}
+
+pub fn blake2s_256(personalization: &[u8; 8], input: &[u8]) -> [u8; OUT_BYTES] {
+    let mut h = IV;
+    // parameter block: digest length, no key, fanout and depth of 1
+    h[0] ^= 0x0101_0000 ^ OUT_BYTES as u32;
+    h[6] ^= u32::from_le_bytes([
+        personalization[0],
+        personalization[1],
+        personalization[2],
+        personalization[3],
+    ]);
+    h[7] ^= u32::from_le_bytes([
+        personalization[4],
+        personalization[5],
+        personalization[6],
+        personalization[7],
+    ]);
+
+    // the last block is always processed with the final flag, even if empty
+    let mut blocks: Vec<&[u8]> = input.chunks(BLOCK_BYTES).collect();
+    if blocks.is_empty() {
+        blocks.push(&[]);
+    }
+    let num_blocks = blocks.len();
+    for (i, chunk) in blocks.into_iter().enumerate() {
+        let mut block = [0u8; BLOCK_BYTES];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let last = i == num_blocks - 1;
+        let counter = if last {
+            input.len() as u64
+        } else {
+            ((i + 1) * BLOCK_BYTES) as u64
+        };
+        compress(&mut h, &block, counter, last);
+    }
+
+    let mut out = [0u8; OUT_BYTES];
+    for (bytes, w) in out.chunks_exact_mut(4).zip(h.iter()) {
+        bytes.copy_from_slice(&w.to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod blake2s_tests {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // test vectors from RFC 7693 and the BLAKE2 reference implementation
+    #[test]
+    fn kats() {
+        assert_eq!(
+            to_hex(&blake2s_256(&[0; 8], b"")),
+            "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+        );
+        assert_eq!(
+            to_hex(&blake2s_256(&[0; 8], b"abc")),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use crate::fields::{bls12::FpBLS12, utils};
+use ff::{Field, PrimeField, SqrtField};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // d = -(10240 / 10241)
+    pub static ref EDWARDS_D: FpBLS12 = {
+        let mut d = utils::from_u64::<FpBLS12>(10241).inverse().unwrap();
+        d.mul_assign(&utils::from_u64(10240));
+        d.negate();
+        d
+    };
+    static ref EDWARDS_D2: FpBLS12 = {
+        let mut d2 = *EDWARDS_D;
+        d2.double();
+        d2
+    };
+}
+
+// Point on the Jubjub curve -u^2 + v^2 = 1 + d u^2 v^2 over the scalar field
+// of BLS12-381 in extended twisted Edwards coordinates (U : V : Z : T) with
+// u = U / Z, v = V / Z and T = U V / Z.
+#[derive(Clone, Copy, Debug)]
+pub struct JubjubPoint {
+    u: FpBLS12,
+    v: FpBLS12,
+    z: FpBLS12,
+    t: FpBLS12,
+}
+
+impl JubjubPoint {
+    pub const COFACTOR: u64 = 8;
+
+    pub fn identity() -> Self {
+        JubjubPoint {
+            u: FpBLS12::zero(),
+            v: FpBLS12::one(),
+            z: FpBLS12::one(),
+            t: FpBLS12::zero(),
+        }
+    }
+
+    // Returns None if (u, v) is not on the curve
+    pub fn from_affine(u: &FpBLS12, v: &FpBLS12) -> Option<Self> {
+        let mut t = *u;
+        t.mul_assign(v);
+        let point = JubjubPoint {
+            u: *u,
+            v: *v,
+            z: FpBLS12::one(),
+            t,
+        };
+        if point.is_on_curve() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    // Decodes the little-endian encoding of v, where the most significant bit
+    // is the sign (i.e., the least significant bit) of u
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let sign = bytes[31] >> 7;
+        let mut repr = <FpBLS12 as PrimeField>::Repr::default();
+        for (limb, chunk) in repr.as_mut().iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut tmp = [0u8; 8];
+            tmp.copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(tmp);
+        }
+        repr.as_mut()[3] &= 0x7FFF_FFFF_FFFF_FFFF;
+        let v = FpBLS12::from_repr(repr).ok()?;
+
+        // u^2 = (v^2 - 1) / (d v^2 + 1)
+        let mut v2 = v;
+        v2.square();
+        let mut num = v2;
+        num.sub_assign(&FpBLS12::one());
+        let mut den = v2;
+        den.mul_assign(&EDWARDS_D);
+        den.add_assign(&FpBLS12::one());
+        num.mul_assign(&den.inverse().unwrap_or_else(FpBLS12::zero));
+
+        let mut u = num.sqrt()?;
+        if (u.into_repr().as_ref()[0] as u8 ^ sign) & 1 == 1 {
+            if u.is_zero() {
+                return None;
+            }
+            u.negate();
+        }
+        Self::from_affine(&u, &v)
+    }
+
+    pub fn to_affine(&self) -> (FpBLS12, FpBLS12) {
+        let z_inv = self.z.inverse().unwrap();
+        let mut u = self.u;
+        u.mul_assign(&z_inv);
+        let mut v = self.v;
+        v.mul_assign(&z_inv);
+        (u, v)
+    }
+
+    pub fn get_u(&self) -> FpBLS12 {
+        self.to_affine().0
+    }
+
+    pub fn get_v(&self) -> FpBLS12 {
+        self.to_affine().1
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.u.is_zero() && self.v == self.z
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        let (u, v) = self.to_affine();
+        let mut u2 = u;
+        u2.square();
+        let mut v2 = v;
+        v2.square();
+
+        let mut lhs = v2;
+        lhs.sub_assign(&u2);
+        let mut rhs = u2;
+        rhs.mul_assign(&v2);
+        rhs.mul_assign(&EDWARDS_D);
+        rhs.add_assign(&FpBLS12::one());
+        lhs == rhs
+    }
+
+    pub fn negate(&mut self) {
+        self.u.negate();
+        self.t.negate();
+    }
+
+    // add-2008-hwcd-3 for a = -1, which is complete since d is not a square
+    pub fn add(&self, other: &Self) -> Self {
+        let mut a = self.v;
+        a.sub_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.sub_assign(&other.u);
+        a.mul_assign(&tmp);
+
+        let mut b = self.v;
+        b.add_assign(&self.u);
+        let mut tmp = other.v;
+        tmp.add_assign(&other.u);
+        b.mul_assign(&tmp);
+
+        let mut c = self.t;
+        c.mul_assign(&EDWARDS_D2);
+        c.mul_assign(&other.t);
+
+        let mut d = self.z;
+        d.double();
+        d.mul_assign(&other.z);
+
+        let mut e = b;
+        e.sub_assign(&a);
+        let mut f = d;
+        f.sub_assign(&c);
+        let mut g = d;
+        g.add_assign(&c);
+        let mut h = b;
+        h.add_assign(&a);
+
+        Self::from_efgh(&e, &f, &g, &h)
+    }
+
+    // dbl-2008-hwcd for a = -1
+    pub fn double(&self) -> Self {
+        let mut a = self.u;
+        a.square();
+        let mut b = self.v;
+        b.square();
+        let mut c = self.z;
+        c.square();
+        c.double();
+
+        let mut e = self.u;
+        e.add_assign(&self.v);
+        e.square();
+        e.sub_assign(&a);
+        e.sub_assign(&b);
+        // G = -A + B, H = -A - B
+        let mut g = b;
+        g.sub_assign(&a);
+        let mut f = g;
+        f.sub_assign(&c);
+        let mut h = a;
+        h.add_assign(&b);
+        h.negate();
+
+        Self::from_efgh(&e, &f, &g, &h)
+    }
+
+    fn from_efgh(e: &FpBLS12, f: &FpBLS12, g: &FpBLS12, h: &FpBLS12) -> Self {
+        let mut u = *e;
+        u.mul_assign(f);
+        let mut v = *g;
+        v.mul_assign(h);
+        let mut z = *f;
+        z.mul_assign(g);
+        let mut t = *e;
+        t.mul_assign(h);
+        JubjubPoint { u, v, z, t }
+    }
+
+    pub fn mul_by_cofactor(&self) -> Self {
+        self.double().double().double()
+    }
+
+    // Fixed-window scalar multiplication with 4 bit windows, where the scalar
+    // is given as little-endian 64 bit limbs
+    pub fn mul(&self, scalar: &[u64]) -> Self {
+        let mut table = [Self::identity(); 16];
+        for i in 1..16 {
+            table[i] = table[i - 1].add(self);
+        }
+
+        let mut res = Self::identity();
+        for limb in scalar.iter().rev() {
+            for window in (0..16).rev() {
+                res = res.double().double().double().double();
+                let index = ((limb >> (4 * window)) & 0xF) as usize;
+                if index != 0 {
+                    res = res.add(&table[index]);
+                }
+            }
+        }
+        res
+    }
+}
+
+impl PartialEq for JubjubPoint {
+    fn eq(&self, other: &Self) -> bool {
+        let mut lhs = self.u;
+        lhs.mul_assign(&other.z);
+        let mut rhs = other.u;
+        rhs.mul_assign(&self.z);
+        if lhs != rhs {
+            return false;
+        }
+        let mut lhs = self.v;
+        lhs.mul_assign(&other.z);
+        let mut rhs = other.v;
+        rhs.mul_assign(&self.z);
+        lhs == rhs
+    }
+}
+
+impl Eq for JubjubPoint {}
+
+#[cfg(test)]
+mod jubjub_tests {
+    use ff::from_hex;
+
+    use crate::pedersen_hash::pedersen_hash_instances::PEDERSEN_HASH_SAPLING_PARAMS;
+
+    use super::*;
+
+    // order of the prime order subgroup
+    static SUBGROUP_ORDER: [u64; 4] = [
+        0xd0970e5ed6f72cb7,
+        0xa6682093ccc81082,
+        0x06673b0101343b00,
+        0x0e7db4ea6533afa9,
+    ];
+
+    #[test]
+    fn edwards_d() {
+        assert_eq!(
+            *EDWARDS_D,
+            from_hex("0x2a9318e74bfa2b48f5fd9207e6bd7fd4292d7f6d37579d2601065fd6d6343eb1").unwrap()
+        );
+    }
+
+    #[test]
+    fn group_law() {
+        let g = PEDERSEN_HASH_SAPLING_PARAMS.get_generators()[0];
+        let identity = JubjubPoint::identity();
+        assert!(identity.is_on_curve());
+        assert_eq!(g.add(&identity), g);
+        assert_eq!(g.add(&g), g.double());
+        assert_eq!(g.double().add(&g), g.add(&g.double()));
+        assert!(g.double().is_on_curve());
+
+        let mut neg = g;
+        neg.negate();
+        assert!(g.add(&neg).is_identity());
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let g = PEDERSEN_HASH_SAPLING_PARAMS.get_generators()[1];
+        assert_eq!(g.mul(&[0]), JubjubPoint::identity());
+        assert_eq!(g.mul(&[1]), g);
+        assert_eq!(g.mul(&[JubjubPoint::COFACTOR]), g.mul_by_cofactor());
+        assert_eq!(g.mul(&[5]), g.double().double().add(&g));
+        assert!(g.mul(&SUBGROUP_ORDER).is_identity());
+
+        let mut order_plus_one = SUBGROUP_ORDER;
+        order_plus_one[0] += 1;
+        assert_eq!(g.mul(&order_plus_one), g);
+    }
+
+    #[test]
+    fn from_bytes() {
+        // v = 1 is the identity, the sign bit is invalid for u = 0
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        assert!(JubjubPoint::from_bytes(&bytes).unwrap().is_identity());
+        bytes[31] = 0x80;
+        assert!(JubjubPoint::from_bytes(&bytes).is_none());
+        // non-canonical encoding of v
+        assert!(JubjubPoint::from_bytes(&[0xFF; 32]).is_none());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod blake2s;
+pub mod jubjub;
+#[allow(clippy::module_inception)]
+pub mod pedersen_hash;
+pub mod pedersen_hash_instances;
+pub mod pedersen_hash_params;
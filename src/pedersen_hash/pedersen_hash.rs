@@ -0,0 +1,177 @@
+use super::{jubjub::JubjubPoint, pedersen_hash_params::PedersenHashParams};
+use crate::{fields::bls12::FpBLS12, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Personalization {
+    NoteCommitment,
+    MerkleTree(usize),
+}
+
+impl Personalization {
+    pub fn get_bits(&self) -> Vec<bool> {
+        match *self {
+            Personalization::NoteCommitment => vec![true; 6],
+            Personalization::MerkleTree(depth) => (0..6).map(|i| (depth >> i) & 1 == 1).collect(),
+        }
+    }
+}
+
+// Pedersen hash of Zcash Sapling
+#[derive(Clone, Debug)]
+pub struct PedersenHash {
+    pub(crate) params: Arc<PedersenHashParams>,
+}
+
+impl PedersenHash {
+    pub fn new(params: &Arc<PedersenHashParams>) -> Self {
+        PedersenHash {
+            params: Arc::clone(params),
+        }
+    }
+
+    // The personalization and input bits are split into segments of
+    // chunks_per_generator 3 bit chunks (a, b, c), each one encoding the
+    // signed digit (1 - 2c) (1 + a + 2b). The j-th chunk of segment i
+    // contributes digit * 2^(4j) * G_i, which is looked up in the tables.
+    pub fn hash_bits<I: IntoIterator<Item = bool>>(
+        &self,
+        personalization: Personalization,
+        bits: I,
+    ) -> JubjubPoint {
+        let mut bits = personalization.get_bits().into_iter().chain(bits);
+        let mut result = JubjubPoint::identity();
+        let mut tables = self.params.tables.iter();
+
+        'segments: loop {
+            let table = tables.next();
+            for j in 0..self.params.chunks_per_generator {
+                let a = match bits.next() {
+                    Some(a) => a,
+                    None => break 'segments,
+                };
+                let b = bits.next().unwrap_or(false);
+                let c = bits.next().unwrap_or(false);
+
+                let table = table.expect("not enough generators");
+                let mut point = table[j][a as usize + 2 * b as usize];
+                if c {
+                    point.negate();
+                }
+                result = result.add(&point);
+            }
+        }
+        result
+    }
+
+    // MerkleCRH of Sapling, where depth 0 is the layer of the leaves. The
+    // inputs are encoded as 255 little-endian bits, the output is the
+    // u-coordinate of the hash.
+    pub fn merkle_hash(&self, depth: usize, lhs: &FpBLS12, rhs: &FpBLS12) -> FpBLS12 {
+        let bits = Self::to_bits(lhs).chain(Self::to_bits(rhs));
+        self.hash_bits(Personalization::MerkleTree(depth), bits)
+            .get_u()
+    }
+
+    fn to_bits(el: &FpBLS12) -> impl Iterator<Item = bool> {
+        let repr = el.into_repr();
+        let limbs = repr.as_ref().to_owned();
+        (0..FpBLS12::NUM_BITS as usize).map(move |i| (limbs[i / 64] >> (i % 64)) & 1 == 1)
+    }
+
+    pub fn hash(&self, el1: &FpBLS12, el2: &FpBLS12) -> FpBLS12 {
+        self.merkle_hash(0, el1, el2)
+    }
+}
+
+// MerkleTreeHash does not provide the depth of a node, hence all layers use
+// the personalization of the leaves. Use merkle_hash for Sapling trees.
+impl MerkleTreeHash<FpBLS12> for PedersenHash {
+    fn compress(&self, input: &[&FpBLS12; 2]) -> FpBLS12 {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod pedersen_hash_tests {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::utils, merkle_tree::merkle_tree_fp::MerkleTree,
+        pedersen_hash::pedersen_hash_instances::PEDERSEN_HASH_SAPLING_PARAMS,
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    // PEDERSEN_HASH_GENERATORS[0] of Zcash
+    #[test]
+    fn generators() {
+        let generators = PEDERSEN_HASH_SAPLING_PARAMS.get_generators();
+        assert_eq!(generators.len(), 6);
+        assert_eq!(
+            generators[0].to_affine(),
+            (
+                from_hex("0x73c016a42ded9578b5ea25de7ec0e3782f0c718f6f0fbadd194e42926f661b51")
+                    .unwrap(),
+                from_hex("0x289e87a2d3521b5779c9166b837edc5ef9472e8bc04e463277bfabd432243cca")
+                    .unwrap()
+            )
+        );
+        for (i, g) in generators.iter().enumerate() {
+            assert!(g.is_on_curve());
+            assert!(!generators[..i].contains(g));
+        }
+    }
+
+    // Only the personalization bits, i.e., the chunks (1, 1, 1) and (1, 1, 1),
+    // which encode -4 - 4 * 16
+    #[test]
+    fn signed_digits() {
+        let pedersen = PedersenHash::new(&PEDERSEN_HASH_SAPLING_PARAMS);
+        let mut expected = PEDERSEN_HASH_SAPLING_PARAMS.get_generators()[0].mul(&[68]);
+        expected.negate();
+        assert_eq!(
+            pedersen.hash_bits(Personalization::NoteCommitment, vec![]),
+            expected
+        );
+    }
+
+    // Root of the empty Sapling note commitment tree of depth 32, where the
+    // uncommitted leaf is 1
+    #[test]
+    fn sapling_empty_root() {
+        let pedersen = PedersenHash::new(&PEDERSEN_HASH_SAPLING_PARAMS);
+        let mut node = Scalar::one();
+        for depth in 0..32 {
+            node = pedersen.merkle_hash(depth, &node, &node);
+        }
+        assert_eq!(
+            node,
+            from_hex("0x3e49b5f954aa9d3545bc6c37744661eea48d7c34e3000d82b7f0010c30f4c2fb").unwrap()
+        );
+    }
+
+    #[test]
+    fn merkle_tree() {
+        let pedersen = PedersenHash::new(&PEDERSEN_HASH_SAPLING_PARAMS);
+        let mut tree = MerkleTree::new(pedersen.clone());
+        for _ in 0..TESTRUNS {
+            let leaves: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let left = pedersen.merkle_hash(0, &leaves[0], &leaves[1]);
+            let right = pedersen.merkle_hash(0, &leaves[2], &leaves[3]);
+            assert_eq!(
+                tree.accumulate(&leaves),
+                pedersen.merkle_hash(0, &left, &right)
+            );
+            assert_ne!(
+                pedersen.merkle_hash(1, &left, &right),
+                pedersen.merkle_hash(0, &left, &right)
+            );
+        }
+    }
+}
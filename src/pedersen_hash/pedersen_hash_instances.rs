@@ -0,0 +1,10 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::pedersen_hash::pedersen_hash_params::PedersenHashParams;
+
+// Sapling: 6 generators with 63 chunks each and personalization "Zcash_PH"
+lazy_static! {
+    pub static ref PEDERSEN_HASH_SAPLING_PARAMS: Arc<PedersenHashParams> =
+        Arc::new(PedersenHashParams::new(6, 63, b"Zcash_PH"));
+}
@@ -0,0 +1,84 @@
+use super::{blake2s::blake2s_256, jubjub::JubjubPoint};
+
+#[derive(Clone, Debug)]
+pub struct PedersenHashParams {
+    pub(crate) chunks_per_generator: usize,
+    pub(crate) generators: Vec<JubjubPoint>,
+    // [k 2^(4j) G_i for k = 1, ..., 4] for each chunk j of each generator G_i
+    pub(crate) tables: Vec<Vec<[JubjubPoint; 4]>>,
+}
+
+impl PedersenHashParams {
+    // first block of the group hash input, a fixed random string chosen for
+    // rigidity in Sapling
+    pub const GH_FIRST_BLOCK: &'static [u8; 64] =
+        b"096b36a5804bfacef1691e173c366a47ff5ba84a44f26ddd7e8d9f79d5b42df0";
+
+    // Generator i is FindGroupHash(personalization, LE32(i)) as in Sapling
+    pub fn new(
+        num_generators: usize,
+        chunks_per_generator: usize,
+        personalization: &[u8; 8],
+    ) -> Self {
+        assert!(num_generators >= 1 && chunks_per_generator >= 1);
+        let generators: Vec<JubjubPoint> = (0..num_generators as u32)
+            .map(|i| Self::find_group_hash(&i.to_le_bytes(), personalization))
+            .collect();
+
+        let tables = generators
+            .iter()
+            .map(|g| {
+                let mut base = *g;
+                (0..chunks_per_generator)
+                    .map(|_| {
+                        let base2 = base.double();
+                        let entry = [base, base2, base2.add(&base), base2.double()];
+                        base = base2.double().double().double();
+                        entry
+                    })
+                    .collect()
+            })
+            .collect();
+
+        PedersenHashParams {
+            chunks_per_generator,
+            generators,
+            tables,
+        }
+    }
+
+    // Appends a counter byte to the tag until the group hash succeeds
+    pub fn find_group_hash(tag: &[u8], personalization: &[u8; 8]) -> JubjubPoint {
+        let mut tag = tag.to_owned();
+        tag.push(0);
+        loop {
+            if let Some(p) = Self::group_hash(&tag, personalization) {
+                return p;
+            }
+            let last = tag.last_mut().unwrap();
+            *last = last.checked_add(1).expect("group hash failed");
+        }
+    }
+
+    // BLAKE2s(GH_FIRST_BLOCK || tag) interpreted as a point and multiplied by
+    // the cofactor, the identity is rejected
+    pub fn group_hash(tag: &[u8], personalization: &[u8; 8]) -> Option<JubjubPoint> {
+        let mut input = Self::GH_FIRST_BLOCK.to_vec();
+        input.extend_from_slice(tag);
+        let h = blake2s_256(personalization, &input);
+        let p = JubjubPoint::from_bytes(&h)?.mul_by_cofactor();
+        if p.is_identity() {
+            None
+        } else {
+            Some(p)
+        }
+    }
+
+    pub fn get_generators(&self) -> &[JubjubPoint] {
+        &self.generators
+    }
+
+    pub fn get_chunks_per_generator(&self) -> usize {
+        self.chunks_per_generator
+    }
+}
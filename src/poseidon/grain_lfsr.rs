@@ -0,0 +1,93 @@
+use ff::{PrimeField, PrimeFieldRepr};
+
+// The 80-bit Grain LFSR in self-shrinking mode, as used by the reference
+// implementation of Poseidon to generate round constants and MDS matrices
+// (https://extgit.iaik.tugraz.at/krypto/hadeshash).
+#[derive(Clone, Debug)]
+pub struct GrainLfsr {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl GrainLfsr {
+    // number of initial clocks which are discarded
+    const WARMUP: usize = 160;
+
+    // Initial state: 2 bits field type (1 for prime fields), 4 bits S-box type
+    // (0 for x^d), 12 bits field size, 12 bits t, 10 bits R_F, 10 bits R_P and
+    // 30 bits set to 1.
+    pub fn new(field_size: usize, t: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let mut state = [false; 80];
+        let mut pos = 0;
+        for (val, bits) in [
+            (1, 2),
+            (0, 4),
+            (field_size, 12),
+            (t, 12),
+            (rounds_f, 10),
+            (rounds_p, 10),
+            ((1 << 30) - 1, 30),
+        ] {
+            for i in (0..bits).rev() {
+                state[pos] = (val >> i) & 1 == 1;
+                pos += 1;
+            }
+        }
+
+        let mut lfsr = GrainLfsr { state, head: 0 };
+        for _ in 0..Self::WARMUP {
+            lfsr.clock();
+        }
+        lfsr
+    }
+
+    // b_{i+80} = b_{i+62} + b_{i+51} + b_{i+38} + b_{i+23} + b_{i+13} + b_i
+    fn clock(&mut self) -> bool {
+        let bit = |offset: usize| self.state[(self.head + offset) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % 80;
+        new_bit
+    }
+
+    // Bits are generated in pairs, the second bit is output if the first one
+    // is 1, otherwise both are discarded.
+    pub fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.clock();
+            let bit = self.clock();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    // F::NUM_BITS bits interpreted as integer, most significant bit first
+    fn next_repr<F: PrimeField>(&mut self) -> F::Repr {
+        let mut repr = F::Repr::default();
+        for _ in 0..F::NUM_BITS {
+            repr.shl(1);
+            repr.as_mut()[0] |= self.next_bit() as u64;
+        }
+        repr
+    }
+
+    // Uniform field element by rejection sampling, used for round constants
+    pub fn field_element<F: PrimeField>(&mut self) -> F {
+        loop {
+            if let Ok(el) = F::from_repr(self.next_repr::<F>()) {
+                return el;
+            }
+        }
+    }
+
+    // Field element reduced modulo p without rejection, used for the MDS
+    // matrix. Since p has F::NUM_BITS bits, a single subtraction suffices.
+    pub fn field_element_mod<F: PrimeField>(&mut self) -> F {
+        let mut repr = self.next_repr::<F>();
+        if repr >= F::char() {
+            repr.sub_noborrow(&F::char());
+        }
+        F::from_repr(repr).unwrap()
+    }
+}
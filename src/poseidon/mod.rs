@@ -0,0 +1,6 @@
+pub mod grain_lfsr;
+#[allow(clippy::module_inception)]
+pub mod poseidon;
+pub mod poseidon_circom;
+pub mod poseidon_instances;
+pub mod poseidon_params;
@@ -0,0 +1,387 @@
+use super::poseidon_params::PoseidonParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon<F: PrimeField, const T: usize = 3> {
+    pub(crate) params: Arc<PoseidonParams<F>>,
+}
+
+impl<F: PrimeField> Poseidon<F> {
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Poseidon<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Poseidon::<F, 5>::new_wide(&params) for 4-ary Merkle trees
+    pub fn new_wide(params: &Arc<PoseidonParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Poseidon {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    // Permutation with the optimized partial rounds
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        let mut round = 0;
+
+        for _ in 0..self.params.rounds_f_beginning {
+            self.full_round(&mut state, round);
+            round += 1;
+        }
+
+        self.add_rc(&mut state, &self.params.opt_round_constants[0]);
+        state = Self::matmul(&self.params.m_i, &state);
+        for r in 0..self.params.rounds_p {
+            state[0] = self.sbox_p(&state[0]);
+            if r + 1 < self.params.rounds_p {
+                state[0].add_assign(&self.params.opt_round_constants[r + 1][0]);
+            }
+            self.sparse_matmul(&mut state, r);
+        }
+        round += self.params.rounds_p;
+
+        for _ in 0..self.params.rounds_f_end {
+            self.full_round(&mut state, round);
+            round += 1;
+        }
+        state
+    }
+
+    // Permutation as specified, i.e., without the optimized partial rounds
+    pub fn permutation_not_opt(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        let rounds_f_end = self.params.rounds_f_beginning + self.params.rounds_p;
+
+        for r in 0..self.params.get_rounds() {
+            self.add_rc(&mut state, &self.params.round_constants[r]);
+            if r < self.params.rounds_f_beginning || r >= rounds_f_end {
+                self.sbox(&mut state);
+            } else {
+                state[0] = self.sbox_p(&state[0]);
+            }
+            state = Self::matmul(&self.params.mds, &state);
+        }
+        state
+    }
+
+    fn full_round(&self, state: &mut [F; T], round: usize) {
+        self.add_rc(state, &self.params.round_constants[round]);
+        self.sbox(state);
+        *state = Self::matmul(&self.params.mds, state);
+    }
+
+    fn add_rc(&self, state: &mut [F; T], rc: &[F]) {
+        for (el, c) in state.iter_mut().zip(rc.iter()) {
+            el.add_assign(c);
+        }
+    }
+
+    fn sbox(&self, state: &mut [F; T]) {
+        for el in state.iter_mut() {
+            *el = self.sbox_p(el);
+        }
+    }
+
+    fn sbox_p(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    fn matmul(mat: &[Vec<F>], input: &[F; T]) -> [F; T] {
+        let mut out = [F::zero(); T];
+        for (row, o) in mat.iter().zip(out.iter_mut()) {
+            for (m, i) in row.iter().zip(input.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                o.add_assign(&tmp);
+            }
+        }
+        out
+    }
+
+    // Multiplication by the sparse matrix (M_00 w_hat^T; v I) of partial round r
+    fn sparse_matmul(&self, state: &mut [F; T], r: usize) {
+        let x0 = state[0];
+        let mut new_x0 = self.params.mds[0][0];
+        new_x0.mul_assign(&x0);
+        for (el, w) in state.iter().skip(1).zip(self.params.w_hat[r].iter()) {
+            let mut tmp = *w;
+            tmp.mul_assign(el);
+            new_x0.add_assign(&tmp);
+        }
+        for (el, v) in state.iter_mut().skip(1).zip(self.params.v[r].iter()) {
+            let mut tmp = *v;
+            tmp.mul_assign(&x0);
+            el.add_assign(&tmp);
+        }
+        state[0] = new_x0;
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Poseidon<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod poseidon_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        poseidon::poseidon_instances::{
+            POSEIDON_BN_PARAMS, POSEIDON_BN_PARAMS_T5, POSEIDON_BN_PARAMS_T9,
+        },
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        state
+            .iter_mut()
+            .for_each(|el| *el = utils::random_scalar(true));
+        state
+    }
+
+    fn opt_equals_not_opt<const T: usize>(params: &Arc<PoseidonParams<Scalar>>) {
+        let poseidon = Poseidon::<Scalar, T>::new_wide(params);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<T>();
+            assert_eq!(
+                poseidon.permutation(&input),
+                poseidon.permutation_not_opt(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let poseidon = Poseidon::new(&POSEIDON_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<3>();
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = random_state::<3>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = poseidon.permutation(&input1);
+            let perm2 = poseidon.permutation(&input1);
+            let perm3 = poseidon.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn opt_equals_not_opt_all() {
+        opt_equals_not_opt::<3>(&POSEIDON_BN_PARAMS);
+        opt_equals_not_opt::<5>(&POSEIDON_BN_PARAMS_T5);
+        opt_equals_not_opt::<9>(&POSEIDON_BN_PARAMS_T9);
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let poseidon = Poseidon::<Scalar, 5>::new_wide(&POSEIDON_BN_PARAMS_T5);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<4>();
+            let mut state = [Scalar::zero(); 5];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(poseidon.hash_n(&input), poseidon.permutation(&state)[0]);
+            assert_eq!(
+                poseidon.compress(&[&input[0], &input[1]]),
+                poseidon.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let poseidon = Poseidon::new(&POSEIDON_BN_PARAMS);
+        let input: [Scalar; 3] = [Scalar::zero(), Scalar::one(), utils::from_u64::<Scalar>(2)];
+        let perm = poseidon.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c").unwrap()
+        );
+
+        let poseidon = Poseidon::<Scalar, 5>::new_wide(&POSEIDON_BN_PARAMS_T5);
+        let mut input = [Scalar::zero(); 5];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            poseidon.permutation(&input)[0],
+            from_hex("0x299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465").unwrap()
+        );
+
+        let poseidon = Poseidon::<Scalar, 9>::new_wide(&POSEIDON_BN_PARAMS_T9);
+        let mut input = [Scalar::zero(); 9];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            poseidon.permutation(&input)[0],
+            from_hex("0x2921ab9bd0140cbc98e40395c0fefb40337a4d54fbbecd9a4d43b3d8d0c4d8d1").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod poseidon_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        poseidon::poseidon_instances::{
+            POSEIDON_BLS_PARAMS, POSEIDON_BLS_PARAMS_T5, POSEIDON_BLS_PARAMS_T9,
+        },
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        state
+            .iter_mut()
+            .for_each(|el| *el = utils::random_scalar(true));
+        state
+    }
+
+    fn opt_equals_not_opt<const T: usize>(params: &Arc<PoseidonParams<Scalar>>) {
+        let poseidon = Poseidon::<Scalar, T>::new_wide(params);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<T>();
+            assert_eq!(
+                poseidon.permutation(&input),
+                poseidon.permutation_not_opt(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let poseidon = Poseidon::new(&POSEIDON_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<3>();
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = random_state::<3>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = poseidon.permutation(&input1);
+            let perm2 = poseidon.permutation(&input1);
+            let perm3 = poseidon.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn opt_equals_not_opt_all() {
+        opt_equals_not_opt::<3>(&POSEIDON_BLS_PARAMS);
+        opt_equals_not_opt::<5>(&POSEIDON_BLS_PARAMS_T5);
+        opt_equals_not_opt::<9>(&POSEIDON_BLS_PARAMS_T9);
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let poseidon = Poseidon::<Scalar, 5>::new_wide(&POSEIDON_BLS_PARAMS_T5);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<4>();
+            let mut state = [Scalar::zero(); 5];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(poseidon.hash_n(&input), poseidon.permutation(&state)[0]);
+            assert_eq!(
+                poseidon.compress(&[&input[0], &input[1]]),
+                poseidon.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let poseidon = Poseidon::new(&POSEIDON_BLS_PARAMS);
+        let input: [Scalar; 3] = [Scalar::zero(), Scalar::one(), utils::from_u64::<Scalar>(2)];
+        let perm = poseidon.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a").unwrap()
+        );
+
+        let poseidon = Poseidon::<Scalar, 5>::new_wide(&POSEIDON_BLS_PARAMS_T5);
+        let mut input = [Scalar::zero(); 5];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            poseidon.permutation(&input)[0],
+            from_hex("0x2a918b9c9f9bd7bb509331c81e297b5707f6fc7393dcee1b13901a0b22202e18").unwrap()
+        );
+
+        let poseidon = Poseidon::<Scalar, 9>::new_wide(&POSEIDON_BLS_PARAMS_T9);
+        let mut input = [Scalar::zero(); 9];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            poseidon.permutation(&input)[0],
+            from_hex("0x2688894f3c2c05834c931351afc38ce5663513c02176e532df28eb2de38071a8").unwrap()
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use super::{poseidon::Poseidon, poseidon_params::PoseidonParams};
+use crate::merkle_tree::merkle_tree_fp::MerkleTreeHash;
+use ff::PrimeField;
+use std::sync::Arc;
+
+// Poseidon as in circomlib's poseidon.circom. n inputs are hashed with width
+// t = n + 1, where the state is initialized to [0, inputs] and the first
+// element of the permuted state is the output.
+#[derive(Clone, Debug)]
+pub struct PoseidonCircom<F: PrimeField> {
+    params: Vec<Arc<PoseidonParams<F>>>,
+}
+
+impl<F: PrimeField> PoseidonCircom<F> {
+    pub const MAX_INPUTS: usize = 16;
+
+    // params[i] has to have width i + 2, i.e., it is used for i + 1 inputs
+    pub fn new(params: &[Arc<PoseidonParams<F>>]) -> Self {
+        assert!(params.len() == Self::MAX_INPUTS);
+        for (i, p) in params.iter().enumerate() {
+            assert!(p.get_t() == i + 2);
+        }
+        PoseidonCircom {
+            params: params.to_owned(),
+        }
+    }
+
+    pub fn hash(&self, inputs: &[F]) -> F {
+        macro_rules! hash_with_width {
+            ($($n:literal),*) => {
+                match inputs.len() {
+                    $($n => self.hash_width::<{ $n + 1 }>(inputs),)*
+                    n => panic!("circomlib Poseidon supports 1 to 16 inputs, got {}", n),
+                }
+            };
+        }
+        hash_with_width!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16)
+    }
+
+    fn hash_width<const T: usize>(&self, inputs: &[F]) -> F {
+        let mut state = [F::zero(); T];
+        state[1..].copy_from_slice(inputs);
+        Poseidon::<F, T>::new_wide(&self.params[T - 2]).permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField> MerkleTreeHash<F> for PoseidonCircom<F> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(&[*input[0], *input[1]])
+    }
+}
+
+#[cfg(test)]
+mod poseidon_circom_tests_bn256 {
+    use ff::Field;
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        merkle_tree::merkle_tree_fp::MerkleTree,
+        poseidon::poseidon_instances::POSEIDON_CIRCOM_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn hash_u64(poseidon: &PoseidonCircom<Scalar>, inputs: &[u64]) -> Scalar {
+        let inputs: Vec<Scalar> = inputs.iter().map(|i| utils::from_u64(*i)).collect();
+        poseidon.hash(&inputs)
+    }
+
+    // test vectors from circomlib
+    #[test]
+    fn kats() {
+        let poseidon = PoseidonCircom::new(&POSEIDON_CIRCOM_BN_PARAMS);
+        let kats: [(&[u64], &str); 8] = [
+            (
+                &[1],
+                "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+            ),
+            (
+                &[1, 2],
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+            ),
+            (
+                &[3, 4],
+                "14763215145315200506921711489642608356394854266165572616578112107564877678998",
+            ),
+            (
+                &[1, 2, 0, 0, 0],
+                "1018317224307729531995786483840663576608797660851238720571059489595066344487",
+            ),
+            (
+                &[3, 4, 5, 10, 23],
+                "13034429309846638789535561449942021891039729847501137143363028890275222221409",
+            ),
+            (
+                &[1, 2, 3, 4, 5, 6],
+                "20400040500897583745843009878988256314335038853985262692600694741116813247201",
+            ),
+            (
+                &[1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0],
+                "5540388656744764564518487011617040650780060800286365721923524861648744699539",
+            ),
+            (
+                &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+                "9989051620750914585850546081941653841776809718687451684622678807385399211877",
+            ),
+        ];
+        for (inputs, expected) in kats {
+            assert_eq!(
+                hash_u64(&poseidon, inputs),
+                Scalar::from_str(expected).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn merkle_tree() {
+        let poseidon = PoseidonCircom::new(&POSEIDON_CIRCOM_BN_PARAMS);
+        let mut tree = MerkleTree::new(poseidon.clone());
+        for _ in 0..TESTRUNS {
+            let leaves: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let left = poseidon.hash(&leaves[..2]);
+            let right = poseidon.hash(&leaves[2..]);
+            assert_eq!(tree.accumulate(&leaves), poseidon.hash(&[left, right]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_inputs() {
+        let poseidon = PoseidonCircom::new(&POSEIDON_CIRCOM_BN_PARAMS);
+        poseidon.hash(&[Scalar::zero(); 17]);
+    }
+}
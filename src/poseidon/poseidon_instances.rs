@@ -0,0 +1,39 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    poseidon::poseidon_params::PoseidonParams,
+};
+
+// circomlib uses x^5 and R_F = 8, R_P for t = 2, ..., 17
+pub static CIRCOM_ROUNDS_P: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+// x^5 with R_F = 8 and R_P from the reference round number script for 128 bit
+// security
+lazy_static! {
+    // BLS12
+    pub static ref POSEIDON_BLS_PARAMS: Arc<PoseidonParams<FpBLS12>> =
+        Arc::new(PoseidonParams::new(3, 5, 8, 57));
+    pub static ref POSEIDON_BLS_PARAMS_T5: Arc<PoseidonParams<FpBLS12>> =
+        Arc::new(PoseidonParams::new(5, 5, 8, 60));
+    pub static ref POSEIDON_BLS_PARAMS_T9: Arc<PoseidonParams<FpBLS12>> =
+        Arc::new(PoseidonParams::new(9, 5, 8, 63));
+
+    // BN256
+    pub static ref POSEIDON_BN_PARAMS: Arc<PoseidonParams<FpBN256>> =
+        Arc::new(PoseidonParams::new(3, 5, 8, 57));
+    pub static ref POSEIDON_BN_PARAMS_T5: Arc<PoseidonParams<FpBN256>> =
+        Arc::new(PoseidonParams::new(5, 5, 8, 60));
+    pub static ref POSEIDON_BN_PARAMS_T9: Arc<PoseidonParams<FpBN256>> =
+        Arc::new(PoseidonParams::new(9, 5, 8, 63));
+
+    // circomlib, widths 2 to 17
+    pub static ref POSEIDON_CIRCOM_BN_PARAMS: Vec<Arc<PoseidonParams<FpBN256>>> = CIRCOM_ROUNDS_P
+        .iter()
+        .enumerate()
+        .map(|(i, rounds_p)| Arc::new(PoseidonParams::new(i + 2, 5, 8, *rounds_p)))
+        .collect();
+}
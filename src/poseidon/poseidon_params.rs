@@ -0,0 +1,268 @@
+use super::grain_lfsr::GrainLfsr;
+use crate::{
+    fields::utils,
+    utils::{mat_inverse, mat_mat_mul, mat_transpose, mat_vec_mul},
+};
+use ff::PrimeField;
+
+type Matrix<F> = Vec<Vec<F>>;
+
+#[derive(Clone, Debug)]
+pub struct PoseidonParams<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) rounds_f_beginning: usize,
+    pub(crate) rounds_p: usize,
+    pub(crate) rounds_f_end: usize,
+    pub(crate) mds: Vec<Vec<F>>,
+    pub(crate) round_constants: Vec<Vec<F>>,
+    // optimized partial rounds: the full round constants of the first partial
+    // round followed by one constant per subsequent partial round
+    pub(crate) opt_round_constants: Vec<Vec<F>>,
+    // dense matrix applied before the first partial round
+    pub(crate) m_i: Vec<Vec<F>>,
+    // first row and first column of the sparse matrix of each partial round
+    pub(crate) w_hat: Vec<Vec<F>>,
+    pub(crate) v: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    // Round constants and the MDS matrix are generated with the Grain LFSR as
+    // in the reference implementation.
+    pub fn new(t: usize, d: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let mut grain = GrainLfsr::new(F::NUM_BITS as usize, t, rounds_f, rounds_p);
+        let round_constants = Self::instantiate_rc(&mut grain, t, rounds_f + rounds_p);
+        let mds = Self::cauchy_mds(&mut grain, t);
+        Self::new_with_constants(t, d, rounds_f, rounds_p, &mds, &round_constants)
+    }
+
+    pub fn new_with_constants(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mds: &[Vec<F>],
+        round_constants: &[Vec<F>],
+    ) -> Self {
+        assert!(t >= 2);
+        assert!(rounds_f.is_multiple_of(2) && rounds_f >= 2 && rounds_p >= 1);
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+        assert!(mds.len() == t && mds.iter().all(|row| row.len() == t));
+        assert!(
+            round_constants.len() == rounds_f + rounds_p
+                && round_constants.iter().all(|rc| rc.len() == t)
+        );
+
+        let rounds_f_beginning = rounds_f / 2;
+        let opt_round_constants =
+            Self::equivalent_round_constants(round_constants, mds, rounds_f_beginning, rounds_p);
+        let (m_i, w_hat, v) = Self::equivalent_matrices(mds, rounds_p);
+
+        PoseidonParams {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds_f_beginning,
+            rounds_p,
+            rounds_f_end: rounds_f - rounds_f_beginning,
+            mds: mds.to_owned(),
+            round_constants: round_constants.to_owned(),
+            opt_round_constants,
+            m_i,
+            w_hat,
+            v,
+        }
+    }
+
+    fn instantiate_rc(grain: &mut GrainLfsr, t: usize, rounds: usize) -> Vec<Vec<F>> {
+        (0..rounds)
+            .map(|_| (0..t).map(|_| grain.field_element()).collect())
+            .collect()
+    }
+
+    // Cauchy matrix M[i][j] = 1 / (x_i + y_j) with pairwise distinct x_i, y_j
+    // and all x_i + y_j != 0. Invalid samples are discarded as a whole.
+    fn cauchy_mds(grain: &mut GrainLfsr, t: usize) -> Vec<Vec<F>> {
+        loop {
+            let vals: Vec<F> = (0..2 * t).map(|_| grain.field_element_mod()).collect();
+            if (1..2 * t).any(|i| vals[..i].contains(&vals[i])) {
+                continue;
+            }
+            let (xs, ys) = vals.split_at(t);
+
+            let mds: Option<Vec<Vec<F>>> = xs
+                .iter()
+                .map(|x| {
+                    ys.iter()
+                        .map(|y| {
+                            let mut sum = *x;
+                            sum.add_assign(y);
+                            sum.inverse()
+                        })
+                        .collect()
+                })
+                .collect();
+            if let Some(mds) = mds {
+                return mds;
+            }
+        }
+    }
+
+    // A partial round M * S(x + c_r) only applies the S-box to x_0. Going
+    // backwards, the constants c_r = M * (u_0, u') are split such that u' is
+    // added before the S-box of the previous round and u_0 after it. Only the
+    // first partial round keeps a full vector.
+    fn equivalent_round_constants(
+        round_constants: &[Vec<F>],
+        mds: &[Vec<F>],
+        rounds_f_beginning: usize,
+        rounds_p: usize,
+    ) -> Vec<Vec<F>> {
+        let mds_inv = mat_inverse(mds);
+        let mut opt = vec![Vec::new(); rounds_p];
+
+        let mut tmp = round_constants[rounds_f_beginning + rounds_p - 1].to_owned();
+        for r in (1..rounds_p).rev() {
+            let inv_c = mat_vec_mul(&mds_inv, &tmp);
+            opt[r] = vec![inv_c[0]];
+            tmp = round_constants[rounds_f_beginning + r - 1].to_owned();
+            for (el, c) in tmp.iter_mut().zip(inv_c.iter()).skip(1) {
+                el.add_assign(c);
+            }
+        }
+        opt[0] = tmp;
+        opt
+    }
+
+    // Going backwards, the matrix N of each partial round is factored into a
+    // sparse matrix (N_00 w_hat^T; v I) and diag(1, N'), where N' is N without
+    // its first row and column. diag(1, N') commutes with the S-box of x_0 and
+    // is merged into the matrix of the previous round. The remaining one is
+    // applied before the first partial round.
+    fn equivalent_matrices(mds: &[Vec<F>], rounds_p: usize) -> (Matrix<F>, Matrix<F>, Matrix<F>) {
+        let t = mds.len();
+        let mut w_hat = vec![Vec::new(); rounds_p];
+        let mut v = vec![Vec::new(); rounds_p];
+
+        let mut n = mds.to_owned();
+        let mut m_i = vec![vec![F::zero(); t]; t];
+        for r in (0..rounds_p).rev() {
+            let n_hat: Vec<Vec<F>> = n[1..].iter().map(|row| row[1..].to_owned()).collect();
+            // w_hat^T = n_row^T * N'^-1
+            let n_hat_inv_t = mat_transpose(&mat_inverse(&n_hat));
+            w_hat[r] = mat_vec_mul(&n_hat_inv_t, &n[0][1..]);
+            v[r] = n[1..].iter().map(|row| row[0]).collect();
+
+            m_i = vec![vec![F::zero(); t]; t];
+            m_i[0][0] = F::one();
+            for (m_row, n_row) in m_i.iter_mut().zip(n.iter()).skip(1) {
+                m_row[1..].copy_from_slice(&n_row[1..]);
+            }
+            n = mat_mat_mul(&m_i, mds);
+        }
+        (m_i, w_hat, v)
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_p + self.rounds_f_end
+    }
+
+    pub fn get_rounds_f(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_f_end
+    }
+
+    pub fn get_rounds_p(&self) -> usize {
+        self.rounds_p
+    }
+
+    pub fn get_mds(&self) -> &[Vec<F>] {
+        &self.mds
+    }
+
+    pub fn get_round_constants(&self) -> &[Vec<F>] {
+        &self.round_constants
+    }
+}
+
+#[cfg(test)]
+mod poseidon_params_tests_bn256 {
+    use ff::from_hex;
+
+    use crate::{fields::bn256::FpBN256, poseidon::poseidon_instances::POSEIDON_BN_PARAMS};
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    #[test]
+    fn reference_constants() {
+        assert_eq!(
+            POSEIDON_BN_PARAMS.get_round_constants()[0][0],
+            from_hex::<Scalar>(
+                "0x0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            POSEIDON_BN_PARAMS.get_mds()[0][0],
+            from_hex::<Scalar>(
+                "0x109b7f411ba0e4c9b2b70caf5c36a7b194be7c11ad24378bfedb68592ba8118b"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_exponent() {
+        PoseidonParams::<Scalar>::new(3, 3, 8, 57);
+    }
+}
+
+#[cfg(test)]
+mod poseidon_params_tests_bls12 {
+    use ff::from_hex;
+
+    use crate::{fields::bls12::FpBLS12, poseidon::poseidon_instances::POSEIDON_BLS_PARAMS};
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    #[test]
+    fn reference_constants() {
+        assert_eq!(
+            POSEIDON_BLS_PARAMS.get_round_constants()[0][0],
+            from_hex::<Scalar>(
+                "0x6c4ffa723eaf1a7bf74905cc7dae4ca9ff4a2c3bc81d42e09540d1f250910880"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            POSEIDON_BLS_PARAMS.get_mds()[0][0],
+            from_hex::<Scalar>(
+                "0x3d955d6c02fe4d7cb500e12f2b55eff668a7b4386bd27413766713c93f2acfcd"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_exponent() {
+        PoseidonParams::<Scalar>::new(3, 3, 8, 57);
+    }
+}
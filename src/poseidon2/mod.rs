@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod poseidon2;
+pub mod poseidon2_instances;
+pub mod poseidon2_params;
@@ -0,0 +1,416 @@
+use super::poseidon2_params::Poseidon2Params;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2<F: PrimeField, const T: usize = 3> {
+    pub(crate) params: Arc<Poseidon2Params<F>>,
+}
+
+impl<F: PrimeField> Poseidon2<F> {
+    pub fn new(params: &Arc<Poseidon2Params<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> Poseidon2<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // Poseidon2::<F, 4>::new_wide(&params) for 3-ary Merkle trees
+    pub fn new_wide(params: &Arc<Poseidon2Params<F>>) -> Self {
+        assert!(params.get_t() == T);
+        Poseidon2 {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+
+        // linear layer at the beginning
+        self.matmul_external(&mut state);
+
+        for rc in self.params.external_rc[..self.params.rounds_f_beginning].iter() {
+            self.external_round(&mut state, rc);
+        }
+
+        for rc in self.params.internal_rc.iter() {
+            state[0].add_assign(rc);
+            state[0] = self.sbox_p(&state[0]);
+            self.matmul_internal(&mut state);
+        }
+
+        for rc in self.params.external_rc[self.params.rounds_f_beginning..].iter() {
+            self.external_round(&mut state, rc);
+        }
+        state
+    }
+
+    fn external_round(&self, state: &mut [F; T], rc: &[F]) {
+        for (el, c) in state.iter_mut().zip(rc.iter()) {
+            el.add_assign(c);
+            *el = self.sbox_p(el);
+        }
+        self.matmul_external(state);
+    }
+
+    fn sbox_p(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    // M_E is circ(2, 1) and circ(2, 1, 1) for t = 2 and t = 3, and
+    // circ(2 M4, M4, ..., M4) for t = 4k
+    pub fn matmul_external(&self, state: &mut [F; T]) {
+        utils::matmul_circ_m4(state);
+    }
+
+    // M_I = 1 + diag(mat_internal_diag_m_1), i.e., x_i' = sum(x) + diag_i * x_i
+    pub fn matmul_internal(&self, state: &mut [F; T]) {
+        let mut sum = state[0];
+        state.iter().skip(1).for_each(|el| sum.add_assign(el));
+        for (el, diag) in state
+            .iter_mut()
+            .zip(self.params.mat_internal_diag_m_1.iter())
+        {
+            el.mul_assign(diag);
+            el.add_assign(&sum);
+        }
+    }
+
+    // For t = 2 there is no capacity, hence the compression mode
+    // P(x)_0 + x_0 with a feed-forward is used instead
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        let mut res = self.permutation(&input)[0];
+        if T == 2 {
+            res.add_assign(el1);
+        }
+        res
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for Poseidon2<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod poseidon2_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        poseidon2::poseidon2_instances::{
+            POSEIDON2_BN_PARAMS, POSEIDON2_BN_PARAMS_T2, POSEIDON2_BN_PARAMS_T4,
+        },
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        state
+            .iter_mut()
+            .for_each(|el| *el = utils::random_scalar(true));
+        state
+    }
+
+    fn matmul<const T: usize>(mat: &[Vec<Scalar>], input: &[Scalar; T]) -> [Scalar; T] {
+        let mut out = [Scalar::zero(); T];
+        for (row, o) in mat.iter().zip(out.iter_mut()) {
+            for (m, i) in row.iter().zip(input.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                o.add_assign(&tmp);
+            }
+        }
+        out
+    }
+
+    // compares the linear layers to the plain matrix multiplications
+    fn linear_layers_with<const T: usize>(params: &Arc<Poseidon2Params<Scalar>>, m_e: &[Vec<u64>]) {
+        let poseidon2 = Poseidon2::<Scalar, T>::new_wide(params);
+        let m_e: Vec<Vec<Scalar>> = m_e
+            .iter()
+            .map(|row| row.iter().map(|el| utils::from_u64(*el)).collect())
+            .collect();
+        let mut m_i = vec![vec![Scalar::one(); T]; T];
+        for (i, row) in m_i.iter_mut().enumerate() {
+            row[i].add_assign(&params.mat_internal_diag_m_1[i]);
+        }
+
+        for _ in 0..TESTRUNS {
+            let input = random_state::<T>();
+            let mut external = input;
+            poseidon2.matmul_external(&mut external);
+            assert_eq!(external, matmul(&m_e, &input));
+            let mut internal = input;
+            poseidon2.matmul_internal(&mut internal);
+            assert_eq!(internal, matmul(&m_i, &input));
+        }
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<3>();
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = random_state::<3>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = poseidon2.permutation(&input1);
+            let perm2 = poseidon2.permutation(&input1);
+            let perm3 = poseidon2.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn linear_layers() {
+        linear_layers_with::<2>(&POSEIDON2_BN_PARAMS_T2, &[vec![2, 1], vec![1, 2]]);
+        linear_layers_with::<3>(
+            &POSEIDON2_BN_PARAMS,
+            &[vec![2, 1, 1], vec![1, 2, 1], vec![1, 1, 2]],
+        );
+        linear_layers_with::<4>(
+            &POSEIDON2_BN_PARAMS_T4,
+            &[
+                vec![5, 7, 1, 3],
+                vec![4, 6, 1, 1],
+                vec![1, 3, 5, 7],
+                vec![1, 1, 4, 6],
+            ],
+        );
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_PARAMS);
+        let compression = Poseidon2::<Scalar, 2>::new_wide(&POSEIDON2_BN_PARAMS_T2);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<2>();
+            assert_eq!(
+                poseidon2.compress(&[&input[0], &input[1]]),
+                poseidon2.permutation(&[input[0], input[1], Scalar::zero()])[0]
+            );
+            let mut expected = compression.permutation(&input)[0];
+            expected.add_assign(&input[0]);
+            assert_eq!(compression.compress(&[&input[0], &input[1]]), expected);
+        }
+    }
+
+    // test vectors from the reference implementation
+    #[test]
+    fn kats() {
+        let poseidon2 = Poseidon2::<Scalar, 2>::new_wide(&POSEIDON2_BN_PARAMS_T2);
+        let mut input = [Scalar::zero(); 2];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = poseidon2.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x1d01e56f49579cec72319e145f06f6177f6c5253206e78c2689781452a31878b").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x0d189ec589c41b8cffa88cfc523618a055abe8192c70f75aa72fc514560f6c61").unwrap()
+        );
+
+        let poseidon2 = Poseidon2::<Scalar, 3>::new_wide(&POSEIDON2_BN_PARAMS);
+        let mut input = [Scalar::zero(); 3];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = poseidon2.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x303b6f7c86d043bfcbcc80214f26a30277a15d3f74ca654992defe7ff8d03570").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x1ed25194542b12eef8617361c3ba7c52e660b145994427cc86296242cf766ec8").unwrap()
+        );
+
+        let poseidon2 = Poseidon2::<Scalar, 4>::new_wide(&POSEIDON2_BN_PARAMS_T4);
+        let mut input = [Scalar::zero(); 4];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = poseidon2.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x01bd538c2ee014ed5141b29e9ae240bf8db3fe5b9a38629a9647cf8d76c01737").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x239b62e7db98aa3a2a8f6a0d2fa1709e7a35959aa6c7034814d9daa90cbac662").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x04cbb44c61d928ed06808456bf758cbf0c18d1e15a7b6dbc8245fa7515d5e3cb").unwrap()
+        );
+        assert_eq!(
+            perm[3],
+            from_hex("0x2e11c5cff2a22c64d01304b778d78f6998eff1ab73163a35603f54794c30847a").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod poseidon2_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        poseidon2::poseidon2_instances::{POSEIDON2_BLS_PARAMS, POSEIDON2_BLS_PARAMS_T2},
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn random_state<const T: usize>() -> [Scalar; T] {
+        let mut state = [Scalar::zero(); T];
+        state
+            .iter_mut()
+            .for_each(|el| *el = utils::random_scalar(true));
+        state
+    }
+
+    fn matmul<const T: usize>(mat: &[Vec<Scalar>], input: &[Scalar; T]) -> [Scalar; T] {
+        let mut out = [Scalar::zero(); T];
+        for (row, o) in mat.iter().zip(out.iter_mut()) {
+            for (m, i) in row.iter().zip(input.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                o.add_assign(&tmp);
+            }
+        }
+        out
+    }
+
+    // compares the linear layers to the plain matrix multiplications
+    fn linear_layers_with<const T: usize>(params: &Arc<Poseidon2Params<Scalar>>, m_e: &[Vec<u64>]) {
+        let poseidon2 = Poseidon2::<Scalar, T>::new_wide(params);
+        let m_e: Vec<Vec<Scalar>> = m_e
+            .iter()
+            .map(|row| row.iter().map(|el| utils::from_u64(*el)).collect())
+            .collect();
+        let mut m_i = vec![vec![Scalar::one(); T]; T];
+        for (i, row) in m_i.iter_mut().enumerate() {
+            row[i].add_assign(&params.mat_internal_diag_m_1[i]);
+        }
+
+        for _ in 0..TESTRUNS {
+            let input = random_state::<T>();
+            let mut external = input;
+            poseidon2.matmul_external(&mut external);
+            assert_eq!(external, matmul(&m_e, &input));
+            let mut internal = input;
+            poseidon2.matmul_internal(&mut internal);
+            assert_eq!(internal, matmul(&m_i, &input));
+        }
+    }
+
+    #[test]
+    fn consistent_perm() {
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1 = random_state::<3>();
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = random_state::<3>();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = poseidon2.permutation(&input1);
+            let perm2 = poseidon2.permutation(&input1);
+            let perm3 = poseidon2.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn linear_layers() {
+        linear_layers_with::<2>(&POSEIDON2_BLS_PARAMS_T2, &[vec![2, 1], vec![1, 2]]);
+        linear_layers_with::<3>(
+            &POSEIDON2_BLS_PARAMS,
+            &[vec![2, 1, 1], vec![1, 2, 1], vec![1, 1, 2]],
+        );
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BLS_PARAMS);
+        let compression = Poseidon2::<Scalar, 2>::new_wide(&POSEIDON2_BLS_PARAMS_T2);
+        for _ in 0..TESTRUNS {
+            let input = random_state::<2>();
+            assert_eq!(
+                poseidon2.compress(&[&input[0], &input[1]]),
+                poseidon2.permutation(&[input[0], input[1], Scalar::zero()])[0]
+            );
+            let mut expected = compression.permutation(&input)[0];
+            expected.add_assign(&input[0]);
+            assert_eq!(compression.compress(&[&input[0], &input[1]]), expected);
+        }
+    }
+
+    // test vectors from the reference implementation
+    #[test]
+    fn kats() {
+        let poseidon2 = Poseidon2::<Scalar, 3>::new_wide(&POSEIDON2_BLS_PARAMS);
+        let mut input = [Scalar::zero(); 3];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = poseidon2.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x1b152349b1950b6a8ca75ee4407b6e26ca5cca5650534e56ef3fd45761fbf5f0").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x4c5793c87d51bdc2c08a32108437dc0000bd0275868f09ebc5f36919af5b3891").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x1fc8ed171e67902ca49863159fe5ba6325318843d13976143b8125f08b50dc6b").unwrap()
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256, utils},
+    poseidon2::poseidon2_params::Poseidon2Params,
+};
+use ff::{from_hex, PrimeField};
+
+// internal matrices 1 + diag(1, 2) and 1 + diag(1, 1, 2) for t = 2 and t = 3
+fn small_diag_m_1<F: PrimeField>(t: usize) -> Vec<F> {
+    let mut diag = vec![F::one(); t];
+    diag[t - 1] = utils::from_u64(2);
+    diag
+}
+
+// x^5 with R_F = 8 and R_P = 56 as in the reference implementation
+lazy_static! {
+    // BLS12
+    pub static ref POSEIDON2_BLS_PARAMS_T2: Arc<Poseidon2Params<FpBLS12>> =
+        Arc::new(Poseidon2Params::new(2, 5, 8, 56, &small_diag_m_1(2)));
+    pub static ref POSEIDON2_BLS_PARAMS: Arc<Poseidon2Params<FpBLS12>> =
+        Arc::new(Poseidon2Params::new(3, 5, 8, 56, &small_diag_m_1(3)));
+
+    // BN256
+    pub static ref MAT_DIAG4_M_1_BN: Vec<FpBN256> = vec![
+        from_hex("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7").unwrap(),
+        from_hex("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b").unwrap(),
+        from_hex("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15").unwrap(),
+        from_hex("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b").unwrap(),
+    ];
+    pub static ref POSEIDON2_BN_PARAMS_T2: Arc<Poseidon2Params<FpBN256>> =
+        Arc::new(Poseidon2Params::new(2, 5, 8, 56, &small_diag_m_1(2)));
+    pub static ref POSEIDON2_BN_PARAMS: Arc<Poseidon2Params<FpBN256>> =
+        Arc::new(Poseidon2Params::new(3, 5, 8, 56, &small_diag_m_1(3)));
+    pub static ref POSEIDON2_BN_PARAMS_T4: Arc<Poseidon2Params<FpBN256>> =
+        Arc::new(Poseidon2Params::new(4, 5, 8, 56, &MAT_DIAG4_M_1_BN));
+}
@@ -0,0 +1,108 @@
+use crate::{fields::utils, poseidon::grain_lfsr::GrainLfsr};
+use ff::PrimeField;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2Params<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) rounds_f_beginning: usize,
+    pub(crate) rounds_p: usize,
+    pub(crate) rounds_f_end: usize,
+    // M_I = 1 + diag(mat_internal_diag_m_1)
+    pub(crate) mat_internal_diag_m_1: Vec<F>,
+    pub(crate) external_rc: Vec<Vec<F>>,
+    pub(crate) internal_rc: Vec<F>,
+}
+
+impl<F: PrimeField> Poseidon2Params<F> {
+    // Round constants are generated with the Grain LFSR as in the reference
+    // implementation: t per external round and one per internal round, in the
+    // order they are used.
+    pub fn new(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mat_internal_diag_m_1: &[F],
+    ) -> Self {
+        let mut grain = GrainLfsr::new(F::NUM_BITS as usize, t, rounds_f, rounds_p);
+        let rounds_f_beginning = rounds_f / 2;
+
+        let mut external_rc: Vec<Vec<F>> = (0..rounds_f_beginning)
+            .map(|_| (0..t).map(|_| grain.field_element()).collect())
+            .collect();
+        let internal_rc: Vec<F> = (0..rounds_p).map(|_| grain.field_element()).collect();
+        external_rc.extend(
+            (rounds_f_beginning..rounds_f).map(|_| (0..t).map(|_| grain.field_element()).collect()),
+        );
+
+        Self::new_with_constants(
+            t,
+            d,
+            rounds_f,
+            rounds_p,
+            mat_internal_diag_m_1,
+            &external_rc,
+            &internal_rc,
+        )
+    }
+
+    pub fn new_with_constants(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mat_internal_diag_m_1: &[F],
+        external_rc: &[Vec<F>],
+        internal_rc: &[F],
+    ) -> Self {
+        assert!(
+            t == 2 || t == 3 || (t >= 4 && t.is_multiple_of(4)),
+            "unsupported width {}",
+            t
+        );
+        assert!(rounds_f.is_multiple_of(2) && rounds_f >= 2 && rounds_p >= 1);
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+        assert!(mat_internal_diag_m_1.len() == t);
+        assert!(external_rc.len() == rounds_f && external_rc.iter().all(|rc| rc.len() == t));
+        assert!(internal_rc.len() == rounds_p);
+
+        let rounds_f_beginning = rounds_f / 2;
+        Poseidon2Params {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            rounds_f_beginning,
+            rounds_p,
+            rounds_f_end: rounds_f - rounds_f_beginning,
+            mat_internal_diag_m_1: mat_internal_diag_m_1.to_owned(),
+            external_rc: external_rc.to_owned(),
+            internal_rc: internal_rc.to_owned(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_p + self.rounds_f_end
+    }
+
+    pub fn get_rounds_f(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_f_end
+    }
+
+    pub fn get_rounds_p(&self) -> usize {
+        self.rounds_p
+    }
+}
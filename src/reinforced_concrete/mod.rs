@@ -1,4 +1,7 @@
 #[allow(clippy::module_inception)]
 pub mod reinforced_concrete;
+pub mod reinforced_concrete_generator;
 pub mod reinforced_concrete_instances;
 pub mod reinforced_concrete_params;
+pub mod reinforced_concrete_sbox;
+pub mod reinforced_concrete_sponge;
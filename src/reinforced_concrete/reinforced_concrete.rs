@@ -1,89 +1,187 @@
-use super::reinforced_concrete_params::ReinforcedConcreteParams;
+use super::reinforced_concrete_params::{
+    DivisionStrategy, ReinforcedConcreteParams, MAX_COMPOSE_GROUPS,
+};
 use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
 use ff::PrimeField;
-use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::{ops::Range, sync::Arc};
+
+// With the constant-time feature, composed elements of the prime fields of this
+// crate are built without the validity check of F::from_raw_repr, the digits
+// are extracted without data dependent branches and the S-box is evaluated by
+// scanning the whole table. The reduction out of the Montgomery form in the
+// decomposition is branch-free in any case.
+cfg_if::cfg_if! {
+    if #[cfg(feature = "constant-time")] {
+        use utils::ct_divide_long_using_recip as divide_long_using_recip;
+        use utils::ct_divide_long_using_recip_u32 as divide_long_using_recip_u32;
+
+        #[inline(always)]
+        fn lookup(table: &[u16], index: u16) -> u16 {
+            utils::ct_lookup(table, index)
+        }
+
+        #[inline(always)]
+        fn from_raw_repr<F: PrimeField>(repr: F::Repr) -> F {
+            // the check only depends on F, other fields keep the validity check
+            if utils::is_crate_field::<F>() {
+                // safe because F is a field of this crate and compose reduces mod p
+                unsafe { utils::ct_from_raw_repr(repr) }
+            } else {
+                F::from_raw_repr(repr).unwrap()
+            }
+        }
+    } else {
+        use utils::{divide_long_using_recip, divide_long_using_recip_u32};
+
+        #[inline(always)]
+        fn lookup(table: &[u16], index: u16) -> u16 {
+            // safe because the tables are padded to the largest s_i in params
+            unsafe { *table.get_unchecked(index as usize) }
+        }
+
+        #[inline(always)]
+        fn from_raw_repr<F: PrimeField>(repr: F::Repr) -> F {
+            F::from_raw_repr(repr).unwrap()
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
-pub struct ReinforcedConcrete<F: PrimeField> {
+pub struct ReinforcedConcrete<F: PrimeField, const T: usize = 3> {
     pub(crate) params: Arc<ReinforcedConcreteParams<F>>,
 }
 
 impl<F: PrimeField> ReinforcedConcrete<F> {
-    #[allow(clippy::assertions_on_constants)]
     pub fn new(params: &Arc<ReinforcedConcreteParams<F>>) -> Self {
-        debug_assert!(ReinforcedConcreteParams::<F>::T == 3);
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> ReinforcedConcrete<F, T> {
+    // number of states permutation_batch processes at once
+    pub const BATCH_CHUNK_SIZE: usize = 16;
+    #[cfg(feature = "parallel")]
+    pub const PARALLEL_CHUNK_SIZE: usize = 256;
+
+    // The width has to match the one of the parameters, e.g.,
+    // ReinforcedConcrete::<F, 4>::new_wide(&params) for 4-ary Merkle trees
+    pub fn new_wide(params: &Arc<ReinforcedConcreteParams<F>>) -> Self {
+        assert!(params.get_t() == T);
         ReinforcedConcrete {
             params: Arc::clone(params),
         }
     }
 
-    pub fn concrete(&self, state: &mut [F; 3], round: usize) {
-        // multiplication by circ(2 1 1) is equal to state + sum(state)
+    pub fn concrete(&self, state: &mut [F; T], round: usize) {
+        if T == 3 {
+            // multiplication by circ(2 1 1) is equal to state + sum(state)
+            let mut sum = state[0];
+            state.iter().skip(1).for_each(|el| sum.add_assign(el));
 
-        let mut sum = state[0];
-        state.iter().skip(1).for_each(|el| sum.add_assign(el));
+            for (el, rc) in state
+                .iter_mut()
+                .zip(self.params.round_constants[round].iter())
+            {
+                el.add_assign(&sum);
+                el.add_assign(rc); // add round constant
+            }
+        } else {
+            *state = Self::mat_vec_mul(&self.params.mds, state);
+            for (el, rc) in state
+                .iter_mut()
+                .zip(self.params.round_constants[round].iter())
+            {
+                el.add_assign(rc); // add round constant
+            }
+        }
+    }
 
-        for (el, rc) in state
-            .iter_mut()
-            .zip(self.params.round_constants[round].iter())
-        {
-            el.add_assign(&sum);
-            el.add_assign(rc); // add round constant
+    fn mat_vec_mul(mat: &[Vec<F>], input: &[F; T]) -> [F; T] {
+        let mut out = [F::zero(); T];
+        for (row, o) in mat.iter().zip(out.iter_mut()) {
+            for (m, i) in row.iter().zip(input.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                o.add_assign(&tmp);
+            }
         }
+        out
     }
 
-    pub fn bricks(&self, state: &[F; 3]) -> [F; 3] {
-        let mut new_state: [F; 3] = [F::zero(); 3];
+    pub fn bricks(&self, state: &[F; T]) -> [F; T] {
+        let mut new_state: [F; T] = [F::zero(); T];
 
-        // squaring
+        // x1
         let mut x1_sq = state[0];
         x1_sq.square();
-        let mut x2_sq = state[1];
-        x2_sq.square();
+        new_state[0] = self.power_map(&state[0], &x1_sq);
 
-        // x1
-        let mut x1 = x1_sq;
-        match self.params.d {
-            3 => {}
-            5 => x1.square(),
-            _ => panic!("not implemented!"),
+        // x_i * (x_{i-1}^2 + alpha_{i-1} * x_{i-1} + beta_{i-1})
+        let mut prev_sq = x1_sq;
+        for i in 1..T {
+            let mut tmp = prev_sq;
+            if i + 1 < T {
+                prev_sq = state[i];
+                prev_sq.square();
+            }
+            for _ in 0..self.params.alphas[i - 1] {
+                tmp.add_assign(&state[i - 1]);
+            }
+            tmp.add_assign(&self.params.betas[i - 1]);
+            tmp.mul_assign(&state[i]);
+            new_state[i] = tmp;
         }
-        x1.mul_assign(&state[0]);
-        new_state[0] = x1;
 
-        // x2
-        for _ in 0..self.params.alphas[0] {
-            x1_sq.add_assign(&state[0]);
-        }
-        x1_sq.add_assign(&self.params.betas[0]);
-        x1_sq.mul_assign(&state[1]);
-        new_state[1] = x1_sq;
+        new_state
+    }
 
-        // x3
-        for _ in 0..self.params.alphas[1] {
-            x2_sq.add_assign(&state[1]);
+    // x^d, where x_sq = x^2 is shared with the next branch of Bricks
+    #[inline(always)]
+    fn power_map(&self, x: &F, x_sq: &F) -> F {
+        match self.params.d {
+            3 => {
+                let mut out = *x_sq;
+                out.mul_assign(x);
+                out
+            }
+            5 => {
+                let mut out = *x_sq;
+                out.square();
+                out.mul_assign(x);
+                out
+            }
+            _ => utils::pow_addition_chain(x, &self.params.d_chain),
         }
-        x2_sq.add_assign(&self.params.betas[1]);
-        x2_sq.mul_assign(&state[2]);
-        new_state[2] = x2_sq;
-
-        new_state
     }
 
     pub fn decompose(&self, val: &F) -> Vec<u16> {
-        let len = self.params.si.len();
-        let mut res = vec![0; len];
-        let mut repr = val.into_repr();
-
-        for i in (1..self.params.si.len()).rev() {
-            let (r, m) = utils::divide_long_using_recip::<F>(
-                &repr,
-                self.params.divisor_i[i],
-                self.params.reciprokal_i[i],
-                self.params.norm_shift_i[i],
-            );
+        let mut res = vec![0; self.params.si.len()];
+        self.decompose_into(val, &mut res);
+        res
+    }
+
+    // The element is reduced out of the Montgomery form by utils::mont_reduce on
+    // the raw limbs, i.e., without F::into_repr and its double width reduction.
+    // Digits are extracted in pairs: One long division by s_{i-1} * s_i, whose
+    // remainder is split into the two digits with single word arithmetic.
+    fn decompose_into(&self, val: &F, res: &mut [u16]) {
+        let mut repr = utils::mont_reduce::<F>(&val.into_raw_repr(), self.params.mont_inv);
+
+        let mut i = self.params.si.len() - 1;
+        while i >= 2 {
+            let (r, m) = self.divide_step(&repr, i);
             repr = r;
-            res[i] = m;
+            let (hi, lo) = self.split_pair(m, i);
+            res[i - 1] = hi;
+            res[i] = lo;
+            i -= 2;
+        }
+        if i == 1 {
+            let (r, m) = self.divide_step(&repr, 1);
+            repr = r;
+            res[1] = m as u16;
         }
 
         res[0] = repr.as_ref()[0] as u16;
@@ -97,72 +195,411 @@ impl<F: PrimeField> ReinforcedConcrete<F> {
                 .skip(1)
                 .for_each(|el| debug_assert!(*el == 0));
         }
+    }
+
+    // Division of step i by s_{i-1} * s_i for i >= 2, or by s_1 for i = 1, with
+    // the strategy selected in params. Crandall division takes a data dependent
+    // number of iterations, hence it is not used with the constant-time feature.
+    #[inline(always)]
+    fn divide_step(&self, repr: &F::Repr, i: usize) -> (F::Repr, u32) {
+        match self.params.division_i[i] {
+            DivisionStrategy::Crandall { k, c } if !cfg!(feature = "constant-time") => {
+                utils::div_mod_crandall_c::<F>(repr, k, c)
+            }
+            _ if i == 1 => {
+                let (r, m) = divide_long_using_recip::<F>(
+                    repr,
+                    self.params.divisor_i[1],
+                    self.params.reciprokal_i[1],
+                    self.params.norm_shift_i[1],
+                );
+                (r, m as u32)
+            }
+            _ => divide_long_using_recip_u32::<F>(
+                repr,
+                self.params.divisor_pair_i[i],
+                self.params.reciprokal_pair_i[i],
+                self.params.norm_shift_pair_i[i],
+            ),
+        }
+    }
 
-        res
+    // Splits m < s_{i-1} * s_i into m / s_i and m % s_i. The quotient is
+    // computed as (m * split_mul_i) >> 64, which is exact for all m < 2^32.
+    //
+    // The S-box is applied to the two digits separately rather than by a paired
+    // table indexed by m. Such a table has s_{i-1} * s_i entries, i.e., about
+    // 460k per pair and 24 MB per direction for the 13 pairs of BN256, and its
+    // random accesses miss the cache: Measured on x86-64, the split with two
+    // lookups into the L1 resident S-box costs about 21 ns per element, the
+    // paired lookups about 105 ns. Under constant-time a scan over the paired
+    // table would be prohibitive as well.
+    #[inline(always)]
+    fn split_pair(&self, m: u32, i: usize) -> (u16, u16) {
+        let hi = ((m as u128 * self.params.split_mul_i[i] as u128) >> 64) as u32;
+        let lo = m - hi * self.params.si[i] as u32;
+        (hi as u16, lo as u16)
     }
 
+    // Composes directly into the Montgomery form. The digits of each group are
+    // combined in a single word, then all groups are combined and converted at
+    // once by a sum of products with two Montgomery reduction steps. This
+    // replaces both the Horner scheme over the full integer and F::from_repr.
     pub fn compose(&self, vals: &[u16]) -> F {
-        let mut repr = F::Repr::default();
-        repr.as_mut()[0] = vals[0] as u64;
+        // the result is reduced mod p, which is only correct for integers < p
+        debug_assert!(
+            ReinforcedConcreteParams::<F>::from_digits(vals, &self.params.si)
+                .is_some_and(|n| n < F::char()),
+            "composed integer is not below p"
+        );
+
+        let mut groups = [0u64; MAX_COMPOSE_GROUPS];
+        let num = self.params.compose_groups.len();
 
-        for (val, s) in vals.iter().zip(self.params.si.iter()).skip(1) {
-            repr = utils::mul_by_single_word::<F>(&repr, *s as u64);
-            repr = utils::add_single_word::<F>(&repr, *val as u64);
+        for ((start, end), group) in self.params.compose_groups.iter().zip(groups.iter_mut()) {
+            let mut acc = vals[*start] as u64;
+            for (val, s) in vals[start + 1..*end]
+                .iter()
+                .zip(self.params.si[start + 1..*end].iter())
+            {
+                acc = acc * *s as u64 + *val as u64;
+            }
+            *group = acc;
         }
-        F::from_repr(repr).unwrap()
+
+        let repr = utils::sum_of_products_redc::<F>(
+            &groups[..num],
+            &self.params.compose_consts,
+            self.params.mont_inv,
+        );
+        from_raw_repr(repr)
     }
 
-    pub fn bars(&self, state: &[F; 3]) -> [F; 3] {
+    pub fn bars(&self, state: &[F; T]) -> [F; T] {
         let mut s = state.to_owned();
+        let mut vals = vec![0; self.params.si.len()];
         for el in s.iter_mut() {
-            let mut vals = self.decompose(el);
+            self.decompose_into(el, &mut vals);
             for val in vals.iter_mut() {
-                // *val = self.params.sbox[*val as usize];
-                // safe because sbox is padded to the correct size in params
-                unsafe {
-                    *val = *self.params.sbox.get_unchecked(*val as usize);
-                }
+                *val = lookup(&self.params.sbox, *val);
             }
             *el = self.compose(&vals);
         }
         s
     }
 
-    pub fn permutation(&self, input: &[F; 3]) -> [F; 3] {
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let pre_rounds = self.params.pre_rounds;
         let mut current_state = input.to_owned();
         // first concrete
         self.concrete(&mut current_state, 0);
 
         // first rounds
-        for i in 1..=ReinforcedConcreteParams::<F>::PRE_ROUNDS {
+        for i in 1..=pre_rounds {
             current_state = self.bricks(&current_state);
             self.concrete(&mut current_state, i);
         }
 
         // bar round
         current_state = self.bars(&current_state);
-        self.concrete(
-            &mut current_state,
-            ReinforcedConcreteParams::<F>::PRE_ROUNDS + 1,
-        );
+        self.concrete(&mut current_state, pre_rounds + 1);
 
         // final rounds
-        for i in ReinforcedConcreteParams::<F>::PRE_ROUNDS + 2
-            ..=ReinforcedConcreteParams::<F>::TOTAL_ROUNDS
-        {
+        for i in pre_rounds + 2..=self.params.get_rounds() {
             current_state = self.bricks(&current_state);
             self.concrete(&mut current_state, i);
         }
         current_state
     }
 
+    // Round 0 is the initial concrete layer, round i > 0 consists of either
+    // bricks or bars followed by concrete.
+    pub fn round(&self, state: &mut [F; T], round: usize) {
+        if round == self.params.pre_rounds + 1 {
+            *state = self.bars(state);
+        } else if round != 0 {
+            *state = self.bricks(state);
+        }
+        self.concrete(state, round);
+    }
+
+    pub fn round_inverse(&self, state: &mut [F; T], round: usize) {
+        self.concrete_inverse(state, round);
+        if round == self.params.pre_rounds + 1 {
+            *state = self.bars_inverse(state);
+        } else if round != 0 {
+            *state = self.bricks_inverse(state);
+        }
+    }
+
+    // Evaluates the given range of rounds, e.g., rounds 2..5
+    pub fn permutation_rounds(&self, input: &[F; T], rounds: Range<usize>) -> [F; T] {
+        assert!(rounds.end <= self.params.get_rounds() + 1);
+        let mut current_state = input.to_owned();
+        for i in rounds {
+            self.round(&mut current_state, i);
+        }
+        current_state
+    }
+
+    // Inverts the given range of rounds, i.e., undoes permutation_rounds
+    pub fn permutation_inverse_rounds(&self, input: &[F; T], rounds: Range<usize>) -> [F; T] {
+        assert!(rounds.end <= self.params.get_rounds() + 1);
+        let mut current_state = input.to_owned();
+        for i in rounds.rev() {
+            self.round_inverse(&mut current_state, i);
+        }
+        current_state
+    }
+
+    pub fn concrete_inverse(&self, state: &mut [F; T], round: usize) {
+        for (el, rc) in state
+            .iter_mut()
+            .zip(self.params.round_constants[round].iter())
+        {
+            el.sub_assign(rc); // remove round constant
+        }
+        *state = Self::mat_vec_mul(&self.params.mds_inv, state);
+    }
+
+    pub fn bricks_inverse(&self, state: &[F; T]) -> [F; T] {
+        let mut new_state: [F; T] = [F::zero(); T];
+
+        // x1 = y1^(1/d)
+        new_state[0] = state[0].pow(self.params.d_inv);
+
+        // x_i = y_i / (x_{i-1}^2 + alpha_{i-1} * x_{i-1} + beta_{i-1})
+        for i in 1..T {
+            let mut tmp = new_state[i - 1];
+            tmp.square();
+            for _ in 0..self.params.alphas[i - 1] {
+                tmp.add_assign(&new_state[i - 1]);
+            }
+            tmp.add_assign(&self.params.betas[i - 1]);
+            new_state[i] = state[i];
+            new_state[i].mul_assign(&tmp.inverse().unwrap());
+        }
+
+        new_state
+    }
+
+    pub fn bars_inverse(&self, state: &[F; T]) -> [F; T] {
+        let mut s = state.to_owned();
+        let mut vals = vec![0; self.params.si.len()];
+        for el in s.iter_mut() {
+            self.decompose_into(el, &mut vals);
+            for val in vals.iter_mut() {
+                *val = lookup(&self.params.sbox_inv, *val);
+            }
+            *el = self.compose(&vals);
+        }
+        s
+    }
+
+    pub fn permutation_inverse(&self, input: &[F; T]) -> [F; T] {
+        let pre_rounds = self.params.pre_rounds;
+        let mut current_state = input.to_owned();
+
+        // final rounds
+        for i in (pre_rounds + 2..=self.params.get_rounds()).rev() {
+            self.concrete_inverse(&mut current_state, i);
+            current_state = self.bricks_inverse(&current_state);
+        }
+
+        // bar round
+        self.concrete_inverse(&mut current_state, pre_rounds + 1);
+        current_state = self.bars_inverse(&current_state);
+
+        // first rounds
+        for i in (1..=pre_rounds).rev() {
+            self.concrete_inverse(&mut current_state, i);
+            current_state = self.bricks_inverse(&current_state);
+        }
+
+        // first concrete
+        self.concrete_inverse(&mut current_state, 0);
+        current_state
+    }
+
     pub fn hash(&self, el1: &F, el2: &F) -> F {
-        let input: [F; 3] = [el1.to_owned(), el2.to_owned(), F::zero()];
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
         self.permutation(&input)[0]
     }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+
+    // Bars on many states at once. All lanes are decomposed digit position by
+    // digit position, such that the divisor and its reciprocal are shared by
+    // all divisions of a position, and the S-box is applied to one contiguous
+    // buffer of digits.
+    fn bars_batch(&self, states: &mut [[F; T]], reprs: &mut Vec<F::Repr>, digits: &mut Vec<u16>) {
+        let len = self.params.si.len();
+        let n = states.len() * T;
+
+        reprs.clear();
+        reprs.extend(states.iter().flat_map(|s| {
+            s.iter()
+                .map(|el| utils::mont_reduce::<F>(&el.into_raw_repr(), self.params.mont_inv))
+        }));
+        digits.resize(n * len, 0);
+
+        // decompose in pairs as in decompose, digits are stored position-major
+        let mut i = len - 1;
+        while i >= 2 {
+            let (hi_digits, lo_digits) = digits[(i - 1) * n..(i + 1) * n].split_at_mut(n);
+            for ((repr, hi), lo) in reprs
+                .iter_mut()
+                .zip(hi_digits.iter_mut())
+                .zip(lo_digits.iter_mut())
+            {
+                let (r, m) = self.divide_step(repr, i);
+                *repr = r;
+                let (h, l) = self.split_pair(m, i);
+                *hi = h;
+                *lo = l;
+            }
+            i -= 2;
+        }
+        if i == 1 {
+            for (repr, digit) in reprs.iter_mut().zip(digits[n..2 * n].iter_mut()) {
+                let (r, m) = self.divide_step(repr, 1);
+                *repr = r;
+                *digit = m as u16;
+            }
+        }
+        for (repr, digit) in reprs.iter().zip(digits[..n].iter_mut()) {
+            *digit = repr.as_ref()[0] as u16;
+        }
+
+        for digit in digits.iter_mut() {
+            *digit = lookup(&self.params.sbox, *digit);
+        }
+
+        // compose
+        let mut vals = vec![0; len];
+        for (k, el) in states.iter_mut().flatten().enumerate() {
+            for (i, val) in vals.iter_mut().enumerate() {
+                *val = digits[i * n + k];
+            }
+            *el = self.compose(&vals);
+        }
+    }
+
+    // Bricks on many states at once. Each branch is updated for all states
+    // before moving on to the next one, such that the multiplications of
+    // different states are independent. The branches are processed from the
+    // last to the first, since branch i depends on the old branch i - 1.
+    fn bricks_batch(&self, states: &mut [[F; T]]) {
+        for i in (2..T).rev() {
+            let alpha = self.params.alphas[i - 1];
+            let beta = &self.params.betas[i - 1];
+            for state in states.iter_mut() {
+                let mut tmp = state[i - 1];
+                tmp.square();
+                for _ in 0..alpha {
+                    tmp.add_assign(&state[i - 1]);
+                }
+                tmp.add_assign(beta);
+                state[i].mul_assign(&tmp);
+            }
+        }
+
+        // the square of x1 is shared by both branches
+        let alpha = self.params.alphas[0];
+        let beta = &self.params.betas[0];
+        for state in states.iter_mut() {
+            let mut x1_sq = state[0];
+            x1_sq.square();
+            let mut tmp = x1_sq;
+            for _ in 0..alpha {
+                tmp.add_assign(&state[0]);
+            }
+            tmp.add_assign(beta);
+            state[1].mul_assign(&tmp);
+            state[0] = self.power_map(&state[0], &x1_sq);
+        }
+    }
+
+    // Concrete on many states at once, with the width dependent branch and the
+    // round constants resolved once per batch
+    fn concrete_batch(&self, states: &mut [[F; T]], round: usize) {
+        let rc = &self.params.round_constants[round];
+        if T == 3 {
+            for state in states.iter_mut() {
+                let mut sum = state[0];
+                state.iter().skip(1).for_each(|el| sum.add_assign(el));
+                for (el, c) in state.iter_mut().zip(rc.iter()) {
+                    el.add_assign(&sum);
+                    el.add_assign(c);
+                }
+            }
+        } else {
+            for state in states.iter_mut() {
+                *state = Self::mat_vec_mul(&self.params.mds, state);
+                for (el, c) in state.iter_mut().zip(rc.iter()) {
+                    el.add_assign(c);
+                }
+            }
+        }
+    }
+
+    // Applies the permutation to all states in place. Equivalent to calling
+    // permutation on each state, but evaluated round by round over the whole
+    // batch.
+    pub fn permutation_batch(&self, states: &mut [[F; T]]) {
+        let n = states.len().min(Self::BATCH_CHUNK_SIZE) * T;
+        let mut reprs = Vec::with_capacity(n);
+        let mut digits = Vec::with_capacity(n * self.params.si.len());
+
+        // the chunks keep the digit buffers in the L1 cache
+        for chunk in states.chunks_mut(Self::BATCH_CHUNK_SIZE) {
+            for round in 0..=self.params.get_rounds() {
+                if round == self.params.pre_rounds + 1 {
+                    self.bars_batch(chunk, &mut reprs, &mut digits);
+                } else if round != 0 {
+                    self.bricks_batch(chunk);
+                }
+                self.concrete_batch(chunk, round);
+            }
+        }
+    }
+
+    pub fn hash_batch(&self, inputs: &[[F; 2]]) -> Vec<F> {
+        let mut states: Vec<[F; T]> = inputs
+            .iter()
+            .map(|input| {
+                let mut state = [F::zero(); T];
+                state[..2].copy_from_slice(input);
+                state
+            })
+            .collect();
+        self.permutation_batch(&mut states);
+        states.iter().map(|state| state[0]).collect()
+    }
+
+    // Parallel version of hash_batch. The inputs are split into chunks, which
+    // are hashed with hash_batch by multiple worker threads.
+    #[cfg(feature = "parallel")]
+    pub fn hash_many(&self, inputs: &[[F; 2]]) -> Vec<F> {
+        inputs
+            .par_chunks(Self::PARALLEL_CHUNK_SIZE)
+            .flat_map_iter(|chunk| self.hash_batch(chunk))
+            .collect()
+    }
 }
 
-impl<F: PrimeField> MerkleTreeHash<F> for ReinforcedConcrete<F> {
+// 2-to-1 compression for all widths, i.e., wider states only add capacity here.
+// (T - 1)-ary trees have to be built on top of hash_n.
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for ReinforcedConcrete<F, T> {
     fn compress(&self, input: &[&F; 2]) -> F {
         self.hash(input[0], input[1])
     }
@@ -173,7 +610,11 @@ mod reinforced_concrete_tests_bn256 {
     use ff::{from_hex, Field};
 
     use crate::{
-        fields::bn256::FpBN256, reinforced_concrete::reinforced_concrete_instances::RC_BN_PARAMS,
+        fields::bn256::FpBN256,
+        reinforced_concrete::reinforced_concrete_instances::{
+            BN256_AB, BN256_SBOX, BN256_SI, RC_BN_PARAMS, RC_BN_PARAMS_T4, RC_BN_PARAMS_T5,
+            RC_BN_PARAMS_T8,
+        },
     };
 
     type Scalar = FpBN256;
@@ -224,6 +665,186 @@ mod reinforced_concrete_tests_bn256 {
         }
     }
 
+    #[test]
+    fn decompose_pairs() {
+        // an additional top digit leaves a single digit after pairing
+        let mut si = vec![2];
+        si.extend_from_slice(&BN256_SI);
+
+        for si in [BN256_SI.to_owned(), si] {
+            let params = Arc::new(ReinforcedConcreteParams::new(
+                5,
+                &si,
+                &BN256_SBOX,
+                BN256_AB.as_ref(),
+            ));
+            let rc = ReinforcedConcrete::new(&params);
+            for _ in 0..TESTRUNS {
+                let input: Scalar = utils::random_scalar(true);
+                let digits = ReinforcedConcreteParams::<Scalar>::digits(&input.into_repr(), &si);
+                assert_eq!(rc.decompose(&input), digits);
+                assert_eq!(rc.compose(&digits), input);
+            }
+        }
+    }
+
+    #[test]
+    fn division_strategies() {
+        assert!(RC_BN_PARAMS
+            .get_division_strategies()
+            .iter()
+            .all(|(_, strategy)| *strategy == DivisionStrategy::Reciprocal));
+
+        // the last pair 32 * 32 = 2^10 is selected for Crandall division, the
+        // pair 1023 * 1025 = 2^20 - 1 only when forced
+        let mut si = BN256_SI.to_owned();
+        si.extend_from_slice(&[1023, 1025, 32, 32]);
+        // Bars has to stay a permutation for the additional digits
+        let min_digit =
+            ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &si)
+                .into_iter()
+                .min()
+                .unwrap();
+        let sbox: Vec<u16> = (0..min_digit).rev().collect();
+        let params = ReinforcedConcreteParams::<Scalar>::new(5, &si, &sbox, BN256_AB.as_ref());
+        let strategies = params.get_division_strategies();
+        assert_eq!(
+            strategies[0],
+            (si.len() - 1, DivisionStrategy::Crandall { k: 10, c: 0 })
+        );
+        assert_eq!(strategies[1], (si.len() - 3, DivisionStrategy::Reciprocal));
+
+        let mut forced = params.clone();
+        for (i, _) in strategies {
+            let divisor = ReinforcedConcreteParams::<Scalar>::division_step_divisor(&si, i);
+            let (k, c) = ReinforcedConcreteParams::<Scalar>::crandall_form(divisor).unwrap();
+            forced.set_division_strategy(i, DivisionStrategy::Crandall { k, c });
+        }
+        let mut calibrated = params.clone();
+        calibrated.calibrate_division(16);
+
+        for params in [params, forced, calibrated] {
+            let rc = ReinforcedConcrete::new(&Arc::new(params));
+            let mut states: Vec<[Scalar; 3]> = (0..TESTRUNS)
+                .map(|_| {
+                    [
+                        utils::random_scalar(true),
+                        utils::random_scalar(true),
+                        utils::random_scalar(true),
+                    ]
+                })
+                .collect();
+            for state in states.iter() {
+                let digits = ReinforcedConcreteParams::<Scalar>::digits(&state[0].into_repr(), &si);
+                assert_eq!(rc.decompose(&state[0]), digits);
+            }
+            let expected: Vec<[Scalar; 3]> = states.iter().map(|s| rc.permutation(s)).collect();
+            rc.permutation_batch(&mut states);
+            assert_eq!(states, expected);
+        }
+    }
+
+    #[test]
+    fn arbitrary_d() {
+        for d in [5, 7, 17] {
+            let params = Arc::new(ReinforcedConcreteParams::new(
+                d,
+                &BN256_SI,
+                &BN256_SBOX,
+                BN256_AB.as_ref(),
+            ));
+            let rc = ReinforcedConcrete::new(&params);
+            let chain = utils::addition_chain(d as u64);
+            for _ in 0..TESTRUNS {
+                let input: [Scalar; 3] = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                let output = rc.bricks(&input);
+                assert_eq!(output[0], input[0].pow([d as u64]));
+                assert_eq!(output[0], utils::pow_addition_chain(&input[0], &chain));
+                assert_eq!(rc.bricks_inverse(&output), input);
+                assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+            }
+        }
+    }
+
+    #[test]
+    fn round_range() {
+        let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        let rounds = rc.params.get_rounds() + 1;
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let perm = rc.permutation(&input);
+            assert_eq!(rc.permutation_rounds(&input, 0..rounds), perm);
+            assert_eq!(rc.permutation_inverse_rounds(&perm, 0..rounds), input);
+
+            let tmp = rc.permutation_rounds(&input, 0..2);
+            let tmp = rc.permutation_rounds(&tmp, 2..5);
+            assert_eq!(rc.permutation_rounds(&tmp, 5..rounds), perm);
+
+            let tmp = rc.permutation_rounds(&input, 2..5);
+            assert_eq!(rc.permutation_inverse_rounds(&tmp, 2..5), input);
+        }
+    }
+
+    #[test]
+    fn reduced_rounds() {
+        let params = Arc::new(ReinforcedConcreteParams::new_with_rounds(
+            5,
+            &BN256_SI,
+            &BN256_SBOX,
+            BN256_AB.as_ref(),
+            1,
+            2,
+        ));
+        assert_eq!(params.get_rounds(), 4);
+        assert_eq!(
+            params.round_constants[..],
+            RC_BN_PARAMS.round_constants[..5]
+        );
+
+        let rc = ReinforcedConcrete::new(&params);
+        let rc_full = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let perm = rc.permutation(&input);
+            assert_eq!(rc.permutation_rounds(&input, 0..5), perm);
+            assert_eq!(rc.permutation_inverse(&perm), input);
+            assert_ne!(rc_full.permutation(&input), perm);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut concrete = input.to_owned();
+            rc.concrete(&mut concrete, 1);
+            rc.concrete_inverse(&mut concrete, 1);
+            assert_eq!(concrete, input);
+            assert_eq!(rc.bricks_inverse(&rc.bricks(&input)), input);
+            assert_eq!(rc.bars_inverse(&rc.bars(&input)), input);
+            assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+            assert_eq!(rc.permutation(&rc.permutation_inverse(&input)), input);
+        }
+    }
+
     #[test]
     fn consistent_hash() {
         let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
@@ -264,6 +885,127 @@ mod reinforced_concrete_tests_bn256 {
             from_hex("0x2c37d92c6d2b6831006bf8b53614f4f5fcc3ee6c5dff9d36a8460625d7ee6907").unwrap(),
         );
     }
+
+    fn wide<const T: usize>(params: &Arc<ReinforcedConcreteParams<Scalar>>) {
+        let rc = ReinforcedConcrete::<Scalar, T>::new_wide(params);
+        for _ in 0..TESTRUNS {
+            let mut input = [Scalar::zero(); T];
+            input
+                .iter_mut()
+                .for_each(|el| *el = utils::random_scalar(true));
+
+            let perm = rc.permutation(&input);
+            assert_eq!(perm, rc.permutation(&input));
+            assert_eq!(rc.permutation_inverse(&perm), input);
+            assert_eq!(rc.bricks_inverse(&rc.bricks(&input)), input);
+
+            let mut state = input;
+            rc.concrete(&mut state, 0);
+            rc.concrete_inverse(&mut state, 0);
+            assert_eq!(state, input);
+
+            assert_eq!(rc.hash_n(&input[..2]), rc.hash(&input[0], &input[1]));
+            assert_ne!(rc.hash_n(&input[..T - 1]), rc.hash_n(&input[1..]));
+
+            let mut states = [input, perm];
+            rc.permutation_batch(&mut states);
+            assert_eq!(states, [perm, rc.permutation(&perm)]);
+        }
+    }
+
+    #[test]
+    fn wide_perm() {
+        wide::<4>(&RC_BN_PARAMS_T4);
+        wide::<5>(&RC_BN_PARAMS_T5);
+        wide::<8>(&RC_BN_PARAMS_T8);
+    }
+
+    #[test]
+    fn batch() {
+        let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        // more than one chunk
+        let n = ReinforcedConcrete::<Scalar>::BATCH_CHUNK_SIZE + TESTRUNS;
+        let mut states: Vec<[Scalar; 3]> = (0..n)
+            .map(|_| {
+                [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ]
+            })
+            .collect();
+        let expected: Vec<[Scalar; 3]> = states.iter().map(|s| rc.permutation(s)).collect();
+        rc.permutation_batch(&mut states);
+        assert_eq!(states, expected);
+
+        let inputs: Vec<[Scalar; 2]> = states.iter().map(|s| [s[0], s[1]]).collect();
+        let expected: Vec<Scalar> = inputs.iter().map(|i| rc.hash(&i[0], &i[1])).collect();
+        assert_eq!(rc.hash_batch(&inputs), expected);
+        assert!(rc.hash_batch(&[]).is_empty());
+
+        let rc = ReinforcedConcrete::<Scalar, 5>::new_wide(&RC_BN_PARAMS_T5);
+        let mut states: Vec<[Scalar; 5]> = (0..TESTRUNS)
+            .map(|_| {
+                let mut state = [Scalar::zero(); 5];
+                state
+                    .iter_mut()
+                    .for_each(|el| *el = utils::random_scalar(true));
+                state
+            })
+            .collect();
+        let expected: Vec<[Scalar; 5]> = states.iter().map(|s| rc.permutation(s)).collect();
+        rc.permutation_batch(&mut states);
+        assert_eq!(states, expected);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn hash_many() {
+        let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        let len = ReinforcedConcrete::<Scalar>::PARALLEL_CHUNK_SIZE * 2 + 3;
+        let inputs: Vec<[Scalar; 2]> = (0..len)
+            .map(|_| [utils::random_scalar(true), utils::random_scalar(true)])
+            .collect();
+        assert_eq!(rc.hash_many(&inputs), rc.hash_batch(&inputs));
+    }
+
+    #[test]
+    fn wide_kats() {
+        let rc = ReinforcedConcrete::<Scalar, 4>::new_wide(&RC_BN_PARAMS_T4);
+        let input: [Scalar; 4] = [
+            Scalar::zero(),
+            Scalar::one(),
+            utils::from_u64(2),
+            utils::from_u64(3),
+        ];
+        let perm = rc.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x02fde028f1c7ac38a4c805fcec60700aa3defc1a3dc8e7809f9f26b89a0ef932").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x14f6af63577e15ceed952754e05616d18282f2c51df7b62d679c981867aabae3").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x284492b6d233d4ae16862a1854198cadd4cb1921cace77c8c499149d248fc02d").unwrap()
+        );
+        assert_eq!(
+            perm[3],
+            from_hex("0x1939099f4f8a01a78ee910fd43bb707da572a8e62a4a95622a229f39535ad2b8").unwrap()
+        );
+        let rc = ReinforcedConcrete::<Scalar, 8>::new_wide(&RC_BN_PARAMS_T8);
+        let mut input = [Scalar::zero(); 8];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = rc.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x166af2217a608608146ac23cd0570c37a6be96d102fcd7e4cb33660d63e59f10").unwrap()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -271,7 +1013,11 @@ mod reinforced_concrete_tests_bls12 {
     use ff::{from_hex, Field};
 
     use crate::{
-        fields::bls12::FpBLS12, reinforced_concrete::reinforced_concrete_instances::RC_BLS_PARAMS,
+        fields::bls12::FpBLS12,
+        reinforced_concrete::reinforced_concrete_instances::{
+            BLS12_AB, BLS12_SBOX, BLS12_SI, RC_BLS_PARAMS, RC_BLS_PARAMS_T4, RC_BLS_PARAMS_T5,
+            RC_BLS_PARAMS_T8,
+        },
     };
 
     type Scalar = FpBLS12;
@@ -322,6 +1068,186 @@ mod reinforced_concrete_tests_bls12 {
         }
     }
 
+    #[test]
+    fn decompose_pairs() {
+        // an additional top digit leaves a single digit after pairing
+        let mut si = vec![2];
+        si.extend_from_slice(&BLS12_SI);
+
+        for si in [BLS12_SI.to_owned(), si] {
+            let params = Arc::new(ReinforcedConcreteParams::new(
+                5,
+                &si,
+                &BLS12_SBOX,
+                BLS12_AB.as_ref(),
+            ));
+            let rc = ReinforcedConcrete::new(&params);
+            for _ in 0..TESTRUNS {
+                let input: Scalar = utils::random_scalar(true);
+                let digits = ReinforcedConcreteParams::<Scalar>::digits(&input.into_repr(), &si);
+                assert_eq!(rc.decompose(&input), digits);
+                assert_eq!(rc.compose(&digits), input);
+            }
+        }
+    }
+
+    #[test]
+    fn division_strategies() {
+        assert!(RC_BLS_PARAMS
+            .get_division_strategies()
+            .iter()
+            .all(|(_, strategy)| *strategy == DivisionStrategy::Reciprocal));
+
+        // the last pair 32 * 32 = 2^10 is selected for Crandall division, the
+        // pair 1023 * 1025 = 2^20 - 1 only when forced
+        let mut si = BLS12_SI.to_owned();
+        si.extend_from_slice(&[1023, 1025, 32, 32]);
+        // Bars has to stay a permutation for the additional digits
+        let min_digit =
+            ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &si)
+                .into_iter()
+                .min()
+                .unwrap();
+        let sbox: Vec<u16> = (0..min_digit).rev().collect();
+        let params = ReinforcedConcreteParams::<Scalar>::new(5, &si, &sbox, BLS12_AB.as_ref());
+        let strategies = params.get_division_strategies();
+        assert_eq!(
+            strategies[0],
+            (si.len() - 1, DivisionStrategy::Crandall { k: 10, c: 0 })
+        );
+        assert_eq!(strategies[1], (si.len() - 3, DivisionStrategy::Reciprocal));
+
+        let mut forced = params.clone();
+        for (i, _) in strategies {
+            let divisor = ReinforcedConcreteParams::<Scalar>::division_step_divisor(&si, i);
+            let (k, c) = ReinforcedConcreteParams::<Scalar>::crandall_form(divisor).unwrap();
+            forced.set_division_strategy(i, DivisionStrategy::Crandall { k, c });
+        }
+        let mut calibrated = params.clone();
+        calibrated.calibrate_division(16);
+
+        for params in [params, forced, calibrated] {
+            let rc = ReinforcedConcrete::new(&Arc::new(params));
+            let mut states: Vec<[Scalar; 3]> = (0..TESTRUNS)
+                .map(|_| {
+                    [
+                        utils::random_scalar(true),
+                        utils::random_scalar(true),
+                        utils::random_scalar(true),
+                    ]
+                })
+                .collect();
+            for state in states.iter() {
+                let digits = ReinforcedConcreteParams::<Scalar>::digits(&state[0].into_repr(), &si);
+                assert_eq!(rc.decompose(&state[0]), digits);
+            }
+            let expected: Vec<[Scalar; 3]> = states.iter().map(|s| rc.permutation(s)).collect();
+            rc.permutation_batch(&mut states);
+            assert_eq!(states, expected);
+        }
+    }
+
+    #[test]
+    fn arbitrary_d() {
+        for d in [5, 7, 17] {
+            let params = Arc::new(ReinforcedConcreteParams::new(
+                d,
+                &BLS12_SI,
+                &BLS12_SBOX,
+                BLS12_AB.as_ref(),
+            ));
+            let rc = ReinforcedConcrete::new(&params);
+            let chain = utils::addition_chain(d as u64);
+            for _ in 0..TESTRUNS {
+                let input: [Scalar; 3] = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                let output = rc.bricks(&input);
+                assert_eq!(output[0], input[0].pow([d as u64]));
+                assert_eq!(output[0], utils::pow_addition_chain(&input[0], &chain));
+                assert_eq!(rc.bricks_inverse(&output), input);
+                assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+            }
+        }
+    }
+
+    #[test]
+    fn round_range() {
+        let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        let rounds = rc.params.get_rounds() + 1;
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let perm = rc.permutation(&input);
+            assert_eq!(rc.permutation_rounds(&input, 0..rounds), perm);
+            assert_eq!(rc.permutation_inverse_rounds(&perm, 0..rounds), input);
+
+            let tmp = rc.permutation_rounds(&input, 0..2);
+            let tmp = rc.permutation_rounds(&tmp, 2..5);
+            assert_eq!(rc.permutation_rounds(&tmp, 5..rounds), perm);
+
+            let tmp = rc.permutation_rounds(&input, 2..5);
+            assert_eq!(rc.permutation_inverse_rounds(&tmp, 2..5), input);
+        }
+    }
+
+    #[test]
+    fn reduced_rounds() {
+        let params = Arc::new(ReinforcedConcreteParams::new_with_rounds(
+            5,
+            &BLS12_SI,
+            &BLS12_SBOX,
+            BLS12_AB.as_ref(),
+            1,
+            2,
+        ));
+        assert_eq!(params.get_rounds(), 4);
+        assert_eq!(
+            params.round_constants[..],
+            RC_BLS_PARAMS.round_constants[..5]
+        );
+
+        let rc = ReinforcedConcrete::new(&params);
+        let rc_full = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let perm = rc.permutation(&input);
+            assert_eq!(rc.permutation_rounds(&input, 0..5), perm);
+            assert_eq!(rc.permutation_inverse(&perm), input);
+            assert_ne!(rc_full.permutation(&input), perm);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut concrete = input.to_owned();
+            rc.concrete(&mut concrete, 1);
+            rc.concrete_inverse(&mut concrete, 1);
+            assert_eq!(concrete, input);
+            assert_eq!(rc.bricks_inverse(&rc.bricks(&input)), input);
+            assert_eq!(rc.bars_inverse(&rc.bars(&input)), input);
+            assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+            assert_eq!(rc.permutation(&rc.permutation_inverse(&input)), input);
+        }
+    }
+
     #[test]
     fn consistent_hash() {
         let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
@@ -362,4 +1288,125 @@ mod reinforced_concrete_tests_bls12 {
             from_hex("0x5abe7c734229be9122f936d919f8babb74b36b1ca98f133b00256e29be115aa8").unwrap(),
         );
     }
+
+    fn wide<const T: usize>(params: &Arc<ReinforcedConcreteParams<Scalar>>) {
+        let rc = ReinforcedConcrete::<Scalar, T>::new_wide(params);
+        for _ in 0..TESTRUNS {
+            let mut input = [Scalar::zero(); T];
+            input
+                .iter_mut()
+                .for_each(|el| *el = utils::random_scalar(true));
+
+            let perm = rc.permutation(&input);
+            assert_eq!(perm, rc.permutation(&input));
+            assert_eq!(rc.permutation_inverse(&perm), input);
+            assert_eq!(rc.bricks_inverse(&rc.bricks(&input)), input);
+
+            let mut state = input;
+            rc.concrete(&mut state, 0);
+            rc.concrete_inverse(&mut state, 0);
+            assert_eq!(state, input);
+
+            assert_eq!(rc.hash_n(&input[..2]), rc.hash(&input[0], &input[1]));
+            assert_ne!(rc.hash_n(&input[..T - 1]), rc.hash_n(&input[1..]));
+
+            let mut states = [input, perm];
+            rc.permutation_batch(&mut states);
+            assert_eq!(states, [perm, rc.permutation(&perm)]);
+        }
+    }
+
+    #[test]
+    fn wide_perm() {
+        wide::<4>(&RC_BLS_PARAMS_T4);
+        wide::<5>(&RC_BLS_PARAMS_T5);
+        wide::<8>(&RC_BLS_PARAMS_T8);
+    }
+
+    #[test]
+    fn batch() {
+        let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        // more than one chunk
+        let n = ReinforcedConcrete::<Scalar>::BATCH_CHUNK_SIZE + TESTRUNS;
+        let mut states: Vec<[Scalar; 3]> = (0..n)
+            .map(|_| {
+                [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ]
+            })
+            .collect();
+        let expected: Vec<[Scalar; 3]> = states.iter().map(|s| rc.permutation(s)).collect();
+        rc.permutation_batch(&mut states);
+        assert_eq!(states, expected);
+
+        let inputs: Vec<[Scalar; 2]> = states.iter().map(|s| [s[0], s[1]]).collect();
+        let expected: Vec<Scalar> = inputs.iter().map(|i| rc.hash(&i[0], &i[1])).collect();
+        assert_eq!(rc.hash_batch(&inputs), expected);
+        assert!(rc.hash_batch(&[]).is_empty());
+
+        let rc = ReinforcedConcrete::<Scalar, 5>::new_wide(&RC_BLS_PARAMS_T5);
+        let mut states: Vec<[Scalar; 5]> = (0..TESTRUNS)
+            .map(|_| {
+                let mut state = [Scalar::zero(); 5];
+                state
+                    .iter_mut()
+                    .for_each(|el| *el = utils::random_scalar(true));
+                state
+            })
+            .collect();
+        let expected: Vec<[Scalar; 5]> = states.iter().map(|s| rc.permutation(s)).collect();
+        rc.permutation_batch(&mut states);
+        assert_eq!(states, expected);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn hash_many() {
+        let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        let len = ReinforcedConcrete::<Scalar>::PARALLEL_CHUNK_SIZE * 2 + 3;
+        let inputs: Vec<[Scalar; 2]> = (0..len)
+            .map(|_| [utils::random_scalar(true), utils::random_scalar(true)])
+            .collect();
+        assert_eq!(rc.hash_many(&inputs), rc.hash_batch(&inputs));
+    }
+
+    #[test]
+    fn wide_kats() {
+        let rc = ReinforcedConcrete::<Scalar, 4>::new_wide(&RC_BLS_PARAMS_T4);
+        let input: [Scalar; 4] = [
+            Scalar::zero(),
+            Scalar::one(),
+            utils::from_u64(2),
+            utils::from_u64(3),
+        ];
+        let perm = rc.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x1e20b6d1d7c9f093fc12a54605411c0f18c9412d66d98689a71784da1b5009d4").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x4db1f7b7f3709291c972b21dd805c7e2f368f8dce6e26d54b2c75250fe64ed3a").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x2e3ce66d9a13caf40ab54ad0b7667074bd32591254205f58ea84256e89ff26f9").unwrap()
+        );
+        assert_eq!(
+            perm[3],
+            from_hex("0x2d6ecffceccc7ad584e6fbd3394029c6bb5f6ca4899ea9449a7240015ae466be").unwrap()
+        );
+        let rc = ReinforcedConcrete::<Scalar, 8>::new_wide(&RC_BLS_PARAMS_T8);
+        let mut input = [Scalar::zero(); 8];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        let perm = rc.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x52d0a5ece211d2f919e3cdb6eec57c460a04c6e5383529fcf711b22739c4d291").unwrap()
+        );
+    }
 }
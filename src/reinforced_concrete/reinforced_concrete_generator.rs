@@ -0,0 +1,381 @@
+use super::{
+    reinforced_concrete_params::ReinforcedConcreteParams,
+    reinforced_concrete_sbox::{SboxCriteria, SboxSynthesizer},
+};
+use crate::fields::utils;
+use ff::PrimeField;
+use std::marker::PhantomData;
+
+// Everything required to reproduce a generated instance via
+// ReinforcedConcreteParams::new
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReinforcedConcreteInstance {
+    pub d: usize,
+    pub si: Vec<u16>,
+    pub v: u16,
+    pub sbox: Vec<u16>,
+    pub ab: Vec<u16>,
+}
+
+impl ReinforcedConcreteInstance {
+    pub fn to_params<F: PrimeField>(&self) -> ReinforcedConcreteParams<F> {
+        ReinforcedConcreteParams::new(self.d, &self.si, &self.sbox, &self.ab)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReinforcedConcreteGenerator<F: PrimeField> {
+    field: PhantomData<F>,
+}
+
+impl<F: PrimeField> ReinforcedConcreteGenerator<F> {
+    // each s_i has to be below 2^10
+    pub const MAX_S: u16 = (1 << 10) - 1;
+
+    pub fn generate() -> (ReinforcedConcreteParams<F>, ReinforcedConcreteInstance) {
+        Self::generate_with_width(ReinforcedConcreteParams::<F>::T)
+    }
+
+    pub fn generate_with_width(
+        t: usize,
+    ) -> (ReinforcedConcreteParams<F>, ReinforcedConcreteInstance) {
+        assert!(ReinforcedConcreteParams::<F>::circulant_row(t).is_some());
+        let d = Self::find_d();
+        let (si, v) = Self::find_decomposition();
+        let (sbox, _) = SboxSynthesizer::new(v, &SboxCriteria::default_for(v), &[]).synthesize();
+        let ab = Self::find_alphas_betas(t - 1);
+
+        let instance = ReinforcedConcreteInstance { d, si, v, sbox, ab };
+        let params = instance.to_params();
+        params.validate().expect("generated parameters are invalid");
+        (params, instance)
+    }
+
+    // smallest d with gcd(d, p-1) = 1
+    pub fn find_d() -> usize {
+        let mut d = 3;
+        while utils::gcd_p_minus_1::<F>(d) != 1 {
+            d += 1;
+        }
+        d as usize
+    }
+
+    // Finds the shortest decomposition such that the smallest digit of p-1 is
+    // as large as possible. Returns the s_i and v, which is the largest prime
+    // not exceeding the smallest digit of p-1.
+    pub fn find_decomposition() -> (Vec<u16>, u16) {
+        let bits = Self::log2(&utils::p_minus_1::<F>());
+        let mut n = f64::ceil(bits / f64::log2(Self::MAX_S as f64)) as usize;
+        loop {
+            if let Some(res) = Self::find_decomposition_with_len(n) {
+                return res;
+            }
+            n += 1;
+        }
+    }
+
+    pub fn find_decomposition_with_len(n: usize) -> Option<(Vec<u16>, u16)> {
+        assert!(n >= 2);
+        let bits = Self::log2(&utils::p_minus_1::<F>());
+        let max_bound = f64::min(
+            f64::floor(f64::exp2(bits / n as f64)),
+            (Self::MAX_S - 1) as f64,
+        ) as u16;
+
+        for bound in (2..=max_bound).rev() {
+            if let Some(si) = Self::find_decomposition_with_bound(n, bound) {
+                let min_digit =
+                    ReinforcedConcreteParams::<F>::digits(&utils::p_minus_1::<F>(), &si)
+                        .into_iter()
+                        .min()
+                        .unwrap();
+                if let Some(v) = Self::largest_prime(min_digit) {
+                    return Some((si, v));
+                }
+            }
+        }
+        None
+    }
+
+    // Greedy search from the least significant digit upwards. Each s_i is
+    // chosen close to the geometric mean of the remaining value such that the
+    // corresponding digit of p-1 is at least bound.
+    fn find_decomposition_with_bound(n: usize, bound: u16) -> Option<Vec<u16>> {
+        let mut x = utils::p_minus_1::<F>();
+        let mut si = vec![0u16; n];
+        let bound_repr = F::Repr::from(bound as u64);
+        let max_repr = F::Repr::from((Self::MAX_S - 1) as u64);
+
+        for i in (1..n).rev() {
+            let target = f64::exp2(Self::log2(&x) / (i + 1) as f64);
+            let mut candidates: Vec<u16> = (bound + 1..=Self::MAX_S).collect();
+            candidates.sort_by(|a, b| {
+                let a = (*a as f64 - target).abs();
+                let b = (*b as f64 - target).abs();
+                a.partial_cmp(&b).unwrap()
+            });
+
+            let mut found = false;
+            for s in candidates {
+                let (q, r) = utils::divide_long::<F>(&x, s);
+                if r < bound {
+                    continue;
+                }
+                // the top digit has to fit as well
+                if i == 1 && (q < bound_repr || q > max_repr) {
+                    continue;
+                }
+                x = q;
+                si[i] = s;
+                found = true;
+                break;
+            }
+            if !found {
+                return None;
+            }
+        }
+
+        si[0] = x.as_ref()[0] as u16 + 1;
+        Some(si)
+    }
+
+    // None if max < 2
+    pub fn largest_prime(max: u16) -> Option<u16> {
+        (2..=max)
+            .rev()
+            .find(|x| (2..*x).take_while(|d| d * d <= *x).all(|d| x % d != 0))
+    }
+
+    // Pairs (alpha, beta) with alpha^2 - 4 beta being a non-square, such that
+    // Bricks is invertible. The pairs are enumerated with increasing
+    // alpha + beta, and the alphas and betas have to be pairwise distinct.
+    // Returns the alphas followed by the betas.
+    pub fn find_alphas_betas(pairs: usize) -> Vec<u16> {
+        let mut res: Vec<(u16, u16)> = Vec::with_capacity(pairs);
+        let mut sum = 2;
+        while res.len() < pairs {
+            for alpha in 1..sum {
+                let beta = sum - alpha;
+                if res.iter().any(|(a, b)| *a == alpha || *b == beta) {
+                    continue;
+                }
+                if Self::is_nonsquare_discriminant(alpha, beta) {
+                    res.push((alpha, beta));
+                    if res.len() == pairs {
+                        break;
+                    }
+                }
+            }
+            sum += 1;
+        }
+        res.iter()
+            .map(|(a, _)| *a)
+            .chain(res.iter().map(|(_, b)| *b))
+            .collect()
+    }
+
+    pub fn is_nonsquare_discriminant(alpha: u16, beta: u16) -> bool {
+        ReinforcedConcreteParams::<F>::is_nonsquare_discriminant(
+            alpha,
+            &utils::from_u64(beta as u64),
+        )
+    }
+
+    fn log2(x: &F::Repr) -> f64 {
+        x.as_ref()
+            .iter()
+            .rev()
+            .fold(0f64, |acc, limb| acc * f64::exp2(64f64) + *limb as f64)
+            .log2()
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_generator_tests_bn256 {
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        reinforced_concrete::{
+            reinforced_concrete::ReinforcedConcrete, reinforced_concrete_instances::*,
+        },
+    };
+    use std::sync::Arc;
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn shipped_instance() {
+        let digits =
+            ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &BN256_SI);
+        let min_digit = digits.into_iter().min().unwrap();
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(min_digit).unwrap() as usize,
+            BN256_SBOX.len()
+        );
+        assert!(
+            ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                BN256_AB[0],
+                BN256_AB[2]
+            )
+        );
+        assert!(
+            ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                BN256_AB[1],
+                BN256_AB[3]
+            )
+        );
+    }
+
+    #[test]
+    fn largest_prime() {
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(0),
+            None
+        );
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(1),
+            None
+        );
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(2),
+            Some(2)
+        );
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(660),
+            Some(659)
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let (params, instance) = ReinforcedConcreteGenerator::<Scalar>::generate();
+        let (_, instance2) = ReinforcedConcreteGenerator::<Scalar>::generate();
+        assert_eq!(instance, instance2);
+        assert_eq!(instance.d, 5);
+
+        let rc = ReinforcedConcrete::new(&Arc::new(params));
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let input2: Scalar = utils::random_scalar(true);
+            assert_eq!(rc.compose(&rc.decompose(&input2)), input2);
+            assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+        }
+    }
+
+    #[test]
+    fn alphas_betas() {
+        for pairs in [2, 7] {
+            let ab = ReinforcedConcreteGenerator::<Scalar>::find_alphas_betas(pairs);
+            assert_eq!(ab.len(), 2 * pairs);
+            for i in 0..pairs {
+                assert!(
+                    ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                        ab[i],
+                        ab[pairs + i]
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decomposition_with_len() {
+        let (si, v) = ReinforcedConcreteGenerator::<Scalar>::find_decomposition_with_len(27)
+            .expect("no decomposition found");
+        assert_eq!(si.len(), 27);
+        let digits = ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &si);
+        assert!(digits.iter().all(|d| *d >= v));
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_generator_tests_bls12 {
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        reinforced_concrete::{
+            reinforced_concrete::ReinforcedConcrete, reinforced_concrete_instances::*,
+        },
+    };
+    use std::sync::Arc;
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn shipped_instance() {
+        let digits =
+            ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &BLS12_SI);
+        let min_digit = digits.into_iter().min().unwrap();
+        assert_eq!(
+            ReinforcedConcreteGenerator::<Scalar>::largest_prime(min_digit).unwrap() as usize,
+            BLS12_SBOX.len()
+        );
+        assert!(
+            ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                BLS12_AB[0],
+                BLS12_AB[2]
+            )
+        );
+        assert!(
+            ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                BLS12_AB[1],
+                BLS12_AB[3]
+            )
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let (params, instance) = ReinforcedConcreteGenerator::<Scalar>::generate();
+        let (_, instance2) = ReinforcedConcreteGenerator::<Scalar>::generate();
+        assert_eq!(instance, instance2);
+        assert_eq!(instance.d, 5);
+
+        let rc = ReinforcedConcrete::new(&Arc::new(params));
+        for _ in 0..TESTRUNS {
+            let input: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+            let input2: Scalar = utils::random_scalar(true);
+            assert_eq!(rc.compose(&rc.decompose(&input2)), input2);
+            assert_eq!(rc.permutation_inverse(&rc.permutation(&input)), input);
+        }
+    }
+
+    #[test]
+    fn alphas_betas() {
+        for pairs in [2, 7] {
+            let ab = ReinforcedConcreteGenerator::<Scalar>::find_alphas_betas(pairs);
+            assert_eq!(ab.len(), 2 * pairs);
+            for i in 0..pairs {
+                assert!(
+                    ReinforcedConcreteGenerator::<Scalar>::is_nonsquare_discriminant(
+                        ab[i],
+                        ab[pairs + i]
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decomposition_with_len() {
+        let (si, v) = ReinforcedConcreteGenerator::<Scalar>::find_decomposition_with_len(27)
+            .expect("no decomposition found");
+        assert_eq!(si.len(), 27);
+        let digits = ReinforcedConcreteParams::<Scalar>::digits(&utils::p_minus_1::<Scalar>(), &si);
+        assert!(digits.iter().all(|d| *d >= v));
+    }
+}
@@ -53,6 +53,16 @@ lazy_static! {
     ];
     pub static ref RC_BLS_PARAMS: Arc<ReinforcedConcreteParams<FpBLS12>> =
         Arc::new(ReinforcedConcreteParams::new(5, &BLS12_SI, &BLS12_SBOX, BLS12_AB.as_ref()));
+    // BLS12, wider states
+    pub static ref BLS12_AB_T4: [u16; 6] = [1,3,5,2,4,1];
+    pub static ref BLS12_AB_T5: [u16; 8] = [1,3,5,2,2,4,1,6];
+    pub static ref BLS12_AB_T8: [u16; 14] = [1,3,5,2,7,4,10,2,4,1,6,3,9,5];
+    pub static ref RC_BLS_PARAMS_T4: Arc<ReinforcedConcreteParams<FpBLS12>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BLS12_SI, &BLS12_SBOX, BLS12_AB_T4.as_ref()));
+    pub static ref RC_BLS_PARAMS_T5: Arc<ReinforcedConcreteParams<FpBLS12>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BLS12_SI, &BLS12_SBOX, BLS12_AB_T5.as_ref()));
+    pub static ref RC_BLS_PARAMS_T8: Arc<ReinforcedConcreteParams<FpBLS12>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BLS12_SI, &BLS12_SBOX, BLS12_AB_T8.as_ref()));
     // BN256
     pub static ref BN256_SI: Vec<u16> = vec![
         673, 678, 667, 683, 680, 655, 683, 683, 681, 683, 675, 668, 675, 677, 680, 681, 669, 683,
@@ -98,4 +108,14 @@ lazy_static! {
     ];
     pub static ref RC_BN_PARAMS: Arc<ReinforcedConcreteParams<FpBN256>> =
         Arc::new(ReinforcedConcreteParams::new(5, &BN256_SI, &BN256_SBOX, BN256_AB.as_ref()));
+    // BN256, wider states
+    pub static ref BN256_AB_T4: [u16; 6] = [1,3,5,2,4,1];
+    pub static ref BN256_AB_T5: [u16; 8] = [1,3,5,2,2,4,1,6];
+    pub static ref BN256_AB_T8: [u16; 14] = [1,3,5,2,9,4,8,2,4,1,6,3,9,5];
+    pub static ref RC_BN_PARAMS_T4: Arc<ReinforcedConcreteParams<FpBN256>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BN256_SI, &BN256_SBOX, BN256_AB_T4.as_ref()));
+    pub static ref RC_BN_PARAMS_T5: Arc<ReinforcedConcreteParams<FpBN256>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BN256_SI, &BN256_SBOX, BN256_AB_T5.as_ref()));
+    pub static ref RC_BN_PARAMS_T8: Arc<ReinforcedConcreteParams<FpBN256>> =
+        Arc::new(ReinforcedConcreteParams::new(5, &BN256_SI, &BN256_SBOX, BN256_AB_T8.as_ref()));
 }
@@ -1,38 +1,145 @@
-use crate::fields::utils;
-use ff::PrimeField;
+use crate::{fields::utils, utils::mat_inverse};
+use ff::{LegendreSymbol, PrimeField};
 use sha3::{
     digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
     Shake128, Shake128ReaderCore,
 };
+use std::{fmt, time::Instant};
+
+// upper bound on the number of digit groups used in composition
+pub const MAX_COMPOSE_GROUPS: usize = 8;
+
+// How the long division of a decomposition step is computed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivisionStrategy {
+    // multiplication by a precomputed reciprocal
+    Reciprocal,
+    // repeated folding for divisors of the form 2^k - c
+    Crandall { k: u32, c: u32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReinforcedConcreteParamsError {
+    InvalidAbLength(usize),
+    UnsupportedWidth(usize),
+    EmptyDecomposition,
+    InvalidDivisor(u16),
+    DecompositionTooSmall,
+    SboxTooLarge { v: usize, min_si: u16 },
+    SboxNotPermutation,
+    SboxExceedsDigits { v: usize, min_digit: u16 },
+    TooManyComposeGroups(usize),
+    NonInvertibleExponent(usize),
+    SquareDiscriminant(usize),
+}
+
+impl fmt::Display for ReinforcedConcreteParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAbLength(len) => {
+                write!(f, "expected t-1 alphas and t-1 betas, got {} values", len)
+            }
+            Self::UnsupportedWidth(t) => write!(f, "no MDS matrix for width {}", t),
+            Self::EmptyDecomposition => write!(f, "si is empty"),
+            Self::InvalidDivisor(s) => write!(f, "invalid s_i {}, has to be at least 2", s),
+            Self::DecompositionTooSmall => {
+                write!(f, "product of all s_i is not larger than the modulus")
+            }
+            Self::SboxTooLarge { v, min_si } => {
+                write!(f, "S-box size {} exceeds the smallest s_i {}", v, min_si)
+            }
+            Self::SboxNotPermutation => write!(f, "S-box is not a permutation of [0, v)"),
+            Self::SboxExceedsDigits { v, min_digit } => write!(
+                f,
+                "S-box size {} exceeds the smallest digit {} of p-1",
+                v, min_digit
+            ),
+            Self::TooManyComposeGroups(groups) => write!(
+                f,
+                "si need {} compose groups, at most {} are supported",
+                groups, MAX_COMPOSE_GROUPS
+            ),
+            Self::NonInvertibleExponent(d) => write!(f, "x^{} is not a permutation", d),
+            Self::SquareDiscriminant(i) => {
+                write!(f, "alpha_{0}^2 - 4 * beta_{0} is a square", i + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReinforcedConcreteParamsError {}
 
 #[derive(Clone, Debug)]
 pub struct ReinforcedConcreteParams<F: PrimeField> {
     pub(crate) round_constants: Vec<Vec<F>>,
-    pub(crate) alphas: [u16; 2],
-    pub(crate) betas: [F; 2],
+    pub(crate) t: usize,
+    pub(crate) mds: Vec<Vec<F>>,
+    pub(crate) mds_inv: Vec<Vec<F>>,
+    pub(crate) alphas: Vec<u16>,
+    pub(crate) betas: Vec<F>,
     pub(crate) si: Vec<u16>,
     pub(crate) divisor_i: Vec<u64>,
     pub(crate) reciprokal_i: Vec<u64>,
     pub(crate) norm_shift_i: Vec<u32>,
+    pub(crate) divisor_pair_i: Vec<u64>,
+    pub(crate) reciprokal_pair_i: Vec<u64>,
+    pub(crate) norm_shift_pair_i: Vec<u32>,
+    pub(crate) split_mul_i: Vec<u64>,
+    pub(crate) division_i: Vec<DivisionStrategy>,
+    pub(crate) compose_groups: Vec<(usize, usize)>,
+    pub(crate) compose_consts: Vec<F::Repr>,
+    pub(crate) mont_inv: u64,
     pub(crate) sbox: Vec<u16>,
+    pub(crate) sbox_inv: Vec<u16>,
+    pub(crate) v: usize,
     pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) d_inv: F::Repr,
+    pub(crate) pre_rounds: usize,
+    pub(crate) post_rounds: usize,
 }
 
 impl<F: PrimeField> ReinforcedConcreteParams<F> {
+    // default number of rounds
     pub const PRE_ROUNDS: usize = 3;
     pub const POST_ROUNDS: usize = 3;
     pub const TOTAL_ROUNDS: usize = Self::PRE_ROUNDS + Self::POST_ROUNDS + 1;
+    // default width
     pub const T: usize = 3;
     pub const INIT_SHAKE: &'static str = "ReinforcedConcrete";
 
     pub fn new(d: usize, si: &[u16], sbox: &[u16], ab: &[u16]) -> Self {
+        Self::new_with_rounds(d, si, sbox, ab, Self::PRE_ROUNDS, Self::POST_ROUNDS)
+    }
+
+    // The width t is given by ab, which consists of t-1 alphas followed by t-1
+    // betas. Round constants of the first rounds do not depend on the number of
+    // rounds, hence reduced-round variants share them with the full version.
+    pub fn new_with_rounds(
+        d: usize,
+        si: &[u16],
+        sbox: &[u16],
+        ab: &[u16],
+        pre_rounds: usize,
+        post_rounds: usize,
+    ) -> Self {
         assert!(sbox.len() <= u16::MAX as usize);
-        assert!(ab.len() == 4);
+        assert!(ab.len() >= 4 && ab.len().is_multiple_of(2));
+        let t = ab.len() / 2 + 1;
+        let mds = Self::circulant_mds(t).expect("unsupported width");
+        assert!(
+            Self::is_invertible_exponent(d),
+            "x^{} is not a permutation",
+            d
+        );
 
         let mut shake = Self::init_shake();
-        let alphas = [ab[0], ab[1]];
-        let betas = [utils::from_u64(ab[2] as u64), utils::from_u64(ab[3] as u64)];
-        let round_constants = Self::instantiate_rc(&mut shake);
+        let alphas = ab[..t - 1].to_owned();
+        let betas = ab[t - 1..]
+            .iter()
+            .map(|b| utils::from_u64(*b as u64))
+            .collect();
+        let round_constants = Self::instantiate_rc(&mut shake, t, pre_rounds + post_rounds + 1);
 
         let len = si.len();
         let mut divisor_i = Vec::with_capacity(len);
@@ -45,17 +152,213 @@ impl<F: PrimeField> ReinforcedConcreteParams<F> {
             norm_shift_i.push((*s as u64).leading_zeros());
         }
 
+        // combined divisors s_{i-1} * s_i for the digits i-1 and i, i >= 2
+        let mut divisor_pair_i = vec![0; len];
+        let mut reciprokal_pair_i = vec![0; len];
+        let mut norm_shift_pair_i = vec![0; len];
+        for i in 2..len {
+            let pair = si[i - 1] as u32 * si[i] as u32;
+            let (div, rec) = utils::compute_normalized_divisor_and_reciproical_u32(pair);
+            divisor_pair_i[i] = div;
+            reciprokal_pair_i[i] = rec;
+            norm_shift_pair_i[i] = (pair as u64).leading_zeros();
+        }
+        let split_mul_i = si.iter().map(|s| (u64::MAX / *s as u64) + 1).collect();
+        // Crandall division is selected for divisors 2^k, where it is a shift and
+        // a mask. Each iteration only removes about k - log2(c) bits otherwise,
+        // and calibrate_division on x86-64 keeps the reciprocal division already
+        // for 2^16 - 1, 2^20 - 1 and 2^22 - 1. Use calibrate_division to measure
+        // on other targets.
+        let mut division_i = vec![DivisionStrategy::Reciprocal; len];
+        for i in Self::division_steps(len) {
+            if let Some((k, 0)) = Self::crandall_form(Self::division_step_divisor(si, i)) {
+                division_i[i] = DivisionStrategy::Crandall { k, c: 0 };
+            }
+        }
+        let (compose_groups, compose_consts) = Self::compose_groups(si);
+
         ReinforcedConcreteParams {
             round_constants,
+            t,
+            mds_inv: mat_inverse(&mds),
+            mds,
             alphas,
             betas,
             si: si.to_owned(),
             divisor_i,
             reciprokal_i,
             norm_shift_i,
+            divisor_pair_i,
+            reciprokal_pair_i,
+            norm_shift_pair_i,
+            split_mul_i,
+            division_i,
+            compose_groups,
+            compose_consts,
+            mont_inv: utils::mont_neg_inv::<F>(),
             sbox: Self::pad_sbox(sbox, si),
+            sbox_inv: Self::pad_sbox(&Self::invert_sbox(sbox), si),
+            v: sbox.len(),
             d,
+            d_chain: utils::addition_chain(d as u64),
+            d_inv: utils::mod_inverse::<F>(d as u16, &utils::p_minus_1::<F>()),
+            pre_rounds,
+            post_rounds,
+        }
+    }
+
+    pub fn try_new(
+        d: usize,
+        si: &[u16],
+        sbox: &[u16],
+        ab: &[u16],
+    ) -> Result<Self, ReinforcedConcreteParamsError> {
+        Self::try_new_with_rounds(d, si, sbox, ab, Self::PRE_ROUNDS, Self::POST_ROUNDS)
+    }
+
+    pub fn try_new_with_rounds(
+        d: usize,
+        si: &[u16],
+        sbox: &[u16],
+        ab: &[u16],
+        pre_rounds: usize,
+        post_rounds: usize,
+    ) -> Result<Self, ReinforcedConcreteParamsError> {
+        // catch everything new() would panic on
+        if ab.len() < 4 || !ab.len().is_multiple_of(2) {
+            return Err(ReinforcedConcreteParamsError::InvalidAbLength(ab.len()));
+        }
+        if Self::circulant_mds(ab.len() / 2 + 1).is_none() {
+            return Err(ReinforcedConcreteParamsError::UnsupportedWidth(
+                ab.len() / 2 + 1,
+            ));
+        }
+        let min_si = *si
+            .iter()
+            .min()
+            .ok_or(ReinforcedConcreteParamsError::EmptyDecomposition)?;
+        if min_si < 2 {
+            return Err(ReinforcedConcreteParamsError::InvalidDivisor(min_si));
+        }
+        let groups = Self::compose_group_bounds(si).len();
+        if groups > MAX_COMPOSE_GROUPS {
+            return Err(ReinforcedConcreteParamsError::TooManyComposeGroups(groups));
+        }
+        if sbox.len() > min_si as usize {
+            return Err(ReinforcedConcreteParamsError::SboxTooLarge {
+                v: sbox.len(),
+                min_si,
+            });
+        }
+        if sbox.iter().any(|s| *s as usize >= sbox.len()) {
+            return Err(ReinforcedConcreteParamsError::SboxNotPermutation);
+        }
+        if !Self::is_invertible_exponent(d) {
+            return Err(ReinforcedConcreteParamsError::NonInvertibleExponent(d));
+        }
+
+        let params = Self::new_with_rounds(d, si, sbox, ab, pre_rounds, post_rounds);
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ReinforcedConcreteParamsError> {
+        // decomposition
+        let min_si = *self
+            .si
+            .iter()
+            .min()
+            .ok_or(ReinforcedConcreteParamsError::EmptyDecomposition)?;
+        if min_si < 2 {
+            return Err(ReinforcedConcreteParamsError::InvalidDivisor(min_si));
+        }
+        let mut top = utils::p_minus_1::<F>();
+        for s in self.si.iter().skip(1) {
+            top = utils::divide_long::<F>(&top, *s).0;
+        }
+        if top >= F::Repr::from(self.si[0] as u64) {
+            return Err(ReinforcedConcreteParamsError::DecompositionTooSmall);
+        }
+        let groups = Self::compose_group_bounds(&self.si).len();
+        if groups > MAX_COMPOSE_GROUPS {
+            return Err(ReinforcedConcreteParamsError::TooManyComposeGroups(groups));
         }
+
+        // sbox
+        if self.v > min_si as usize {
+            return Err(ReinforcedConcreteParamsError::SboxTooLarge { v: self.v, min_si });
+        }
+        let mut seen = vec![false; self.v];
+        for s in self.sbox.iter().take(self.v) {
+            let s = *s as usize;
+            if s >= self.v || seen[s] {
+                return Err(ReinforcedConcreteParamsError::SboxNotPermutation);
+            }
+            seen[s] = true;
+        }
+        // Bars is only a permutation if no digit of p-1 is below v
+        let min_digit = Self::digits(&utils::p_minus_1::<F>(), &self.si)
+            .into_iter()
+            .min()
+            .unwrap();
+        if self.v > min_digit as usize {
+            return Err(ReinforcedConcreteParamsError::SboxExceedsDigits {
+                v: self.v,
+                min_digit,
+            });
+        }
+
+        // bricks
+        if !Self::is_invertible_exponent(self.d) {
+            return Err(ReinforcedConcreteParamsError::NonInvertibleExponent(self.d));
+        }
+        for (i, (alpha, beta)) in self.alphas.iter().zip(self.betas.iter()).enumerate() {
+            if !Self::is_nonsquare_discriminant(*alpha, beta) {
+                return Err(ReinforcedConcreteParamsError::SquareDiscriminant(i));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_invertible_exponent(d: usize) -> bool {
+        d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1
+    }
+
+    // digits of val in the mixed radix representation defined by si, most
+    // significant first
+    pub fn digits(val: &F::Repr, si: &[u16]) -> Vec<u16> {
+        let mut res = vec![0; si.len()];
+        let mut x = val.to_owned();
+        for i in (1..si.len()).rev() {
+            let (q, r) = utils::divide_long::<F>(&x, si[i]);
+            x = q;
+            res[i] = r;
+        }
+        res[0] = x.as_ref()[0] as u16;
+        res
+    }
+
+    // inverse of digits, None if the integer does not fit into F::Repr
+    pub fn from_digits(digits: &[u16], si: &[u16]) -> Option<F::Repr> {
+        let mut res = F::Repr::from(digits[0] as u64);
+        for (digit, s) in digits.iter().zip(si.iter()).skip(1) {
+            let (tmp, carry) = utils::mul_by_single_word_carry::<F>(&res, *s as u64);
+            res = utils::add_single_word::<F>(&tmp, *digit as u64);
+            if carry != 0 || res < tmp {
+                return None;
+            }
+        }
+        Some(res)
+    }
+
+    pub fn is_nonsquare_discriminant(alpha: u16, beta: &F) -> bool {
+        let mut disc = utils::from_u64::<F>(alpha as u64 * alpha as u64);
+        let mut four_beta = *beta;
+        four_beta.double();
+        four_beta.double();
+        disc.sub_assign(&four_beta);
+        utils::legendre(&disc) == LegendreSymbol::QuadraticNonResidue
     }
 
     fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
@@ -67,6 +370,165 @@ impl<F: PrimeField> ReinforcedConcreteParams<F> {
         shake.finalize_xof()
     }
 
+    // Splits the digits into groups (start, end) whose s_i multiply to less than
+    // 2^63, such that each group can be composed in a single word. The constant
+    // of a group is the product of all subsequent s_i, multiplied by R * 2^128
+    // for utils::sum_of_products_redc.
+    fn compose_groups(si: &[u16]) -> (Vec<(usize, usize)>, Vec<F::Repr>) {
+        let groups = Self::compose_group_bounds(si);
+        assert!(groups.len() <= MAX_COMPOSE_GROUPS);
+
+        let mut weight = utils::from_u64::<F>(2).pow([128]);
+        let mut consts = vec![F::Repr::default(); groups.len()];
+        for ((start, end), c) in groups.iter().zip(consts.iter_mut()).rev() {
+            *c = weight.into_raw_repr();
+            for s in si[*start..*end].iter() {
+                weight.mul_assign(&utils::from_u64(*s as u64));
+            }
+        }
+        (groups, consts)
+    }
+
+    fn compose_group_bounds(si: &[u16]) -> Vec<(usize, usize)> {
+        let mut groups = Vec::new();
+        let mut start = 0;
+        let mut prod = si[0] as u64;
+        for (i, s) in si.iter().enumerate().skip(1) {
+            match prod.checked_mul(*s as u64) {
+                Some(tmp) if tmp >> 63 == 0 => prod = tmp,
+                _ => {
+                    groups.push((start, i));
+                    start = i;
+                    prod = *s as u64;
+                }
+            }
+        }
+        groups.push((start, si.len()));
+        groups
+    }
+
+    // Indices i of the division steps of the decomposition, in the order they
+    // are computed. Step i >= 2 extracts the digits i-1 and i, step 1 only
+    // extracts digit 1.
+    pub fn division_steps(len: usize) -> Vec<usize> {
+        let mut steps: Vec<usize> = (2..len).rev().step_by(2).collect();
+        if len.is_multiple_of(2) {
+            steps.push(1);
+        }
+        steps
+    }
+
+    pub fn division_step_divisor(si: &[u16], i: usize) -> u32 {
+        match i {
+            1 => si[1] as u32,
+            _ => si[i - 1] as u32 * si[i] as u32,
+        }
+    }
+
+    // Writes divisor as 2^k - c with c < 2^(k-1)
+    pub fn crandall_form(divisor: u32) -> Option<(u32, u32)> {
+        if divisor < 2 {
+            return None;
+        }
+        if divisor.is_power_of_two() {
+            return Some((divisor.trailing_zeros(), 0));
+        }
+        let k = 32 - divisor.leading_zeros();
+        if k > 31 {
+            return None;
+        }
+        Some((k, (1u32 << k) - divisor))
+    }
+
+    // The strategy of each division step, indexed as in division_steps. Can be
+    // recorded and applied with set_division_strategy to reproduce a
+    // calibration. By default, only divisors 2^k use Crandall division and all
+    // others the reciprocal one, such that the shipped BN256 and BLS12 instances
+    // are reciprocal-only. calibrate_division is the supported way to select
+    // Crandall division for divisors 2^k - c with small c.
+    pub fn get_division_strategies(&self) -> Vec<(usize, DivisionStrategy)> {
+        Self::division_steps(self.si.len())
+            .into_iter()
+            .map(|i| (i, self.division_i[i]))
+            .collect()
+    }
+
+    pub fn set_division_strategy(&mut self, i: usize, strategy: DivisionStrategy) {
+        assert!(
+            Self::division_steps(self.si.len()).contains(&i),
+            "no division step {}",
+            i
+        );
+        if let DivisionStrategy::Crandall { k, c } = strategy {
+            assert_eq!(
+                Self::crandall_form(Self::division_step_divisor(&self.si, i)),
+                Some((k, c)),
+                "divisor of step {} is not 2^{} - {}",
+                i,
+                k,
+                c
+            );
+        }
+        self.division_i[i] = strategy;
+    }
+
+    // Times both strategies for each division step on samples random field
+    // elements and keeps the faster one. Crandall division is only considered
+    // for c <= 2^(k/2), i.e., when each iteration removes at least k/2 bits.
+    pub fn calibrate_division(&mut self, samples: usize) {
+        const REPETITIONS: usize = 5;
+        let mut reprs: Vec<F::Repr> = (0..samples.max(1))
+            .map(|_| utils::random_scalar::<F>(true).into_repr())
+            .collect();
+
+        for i in Self::division_steps(self.si.len()) {
+            let divisor = Self::division_step_divisor(&self.si, i);
+            let (div, rec) = utils::compute_normalized_divisor_and_reciproical_u32(divisor);
+            let norm_shift = (divisor as u64).leading_zeros();
+            let reciprocal = || {
+                let start = Instant::now();
+                for repr in reprs.iter() {
+                    std::hint::black_box(utils::divide_long_using_recip_u32::<F>(
+                        std::hint::black_box(repr),
+                        div,
+                        rec,
+                        norm_shift,
+                    ));
+                }
+                start.elapsed()
+            };
+            let crandall = |k, c| {
+                let start = Instant::now();
+                for repr in reprs.iter() {
+                    std::hint::black_box(utils::div_mod_crandall_c::<F>(
+                        std::hint::black_box(repr),
+                        k,
+                        c,
+                    ));
+                }
+                start.elapsed()
+            };
+
+            self.division_i[i] = match Self::crandall_form(divisor) {
+                Some((k, c)) if (c as u64) <= 1u64 << (k / 2) => {
+                    let t_rec = (0..REPETITIONS).map(|_| reciprocal()).min().unwrap();
+                    let t_cr = (0..REPETITIONS).map(|_| crandall(k, c)).min().unwrap();
+                    if t_cr < t_rec {
+                        DivisionStrategy::Crandall { k, c }
+                    } else {
+                        DivisionStrategy::Reciprocal
+                    }
+                }
+                _ => DivisionStrategy::Reciprocal,
+            };
+
+            // the next step divides the quotients
+            for repr in reprs.iter_mut() {
+                *repr = utils::divide_long_using_recip_u32::<F>(repr, div, rec, norm_shift).0;
+            }
+        }
+    }
+
     fn pad_sbox(sbox: &[u16], si: &[u16]) -> Vec<u16> {
         let len = sbox.len();
 
@@ -81,10 +543,46 @@ impl<F: PrimeField> ReinforcedConcreteParams<F> {
         out
     }
 
-    fn instantiate_rc(shake: &mut dyn XofReader) -> Vec<Vec<F>> {
-        (0..=Self::TOTAL_ROUNDS)
+    fn invert_sbox(sbox: &[u16]) -> Vec<u16> {
+        let mut inv = vec![0; sbox.len()];
+        for (i, s) in sbox.iter().enumerate() {
+            inv[*s as usize] = i as u16;
+        }
+        inv
+    }
+
+    // First rows of circulant MDS matrices, i.e., M[i][j] = row[(j - i) mod t].
+    // All minors are non-zero over the integers and small enough not to vanish
+    // modulo a large prime.
+    pub fn circulant_row(t: usize) -> Option<&'static [u64]> {
+        match t {
+            3 => Some(&[2, 1, 1]),
+            4 => Some(&[1, 1, 2, 3]),
+            5 => Some(&[1, 1, 2, 3, 2]),
+            6 => Some(&[5, 1, 3, 2, 2, 1]),
+            7 => Some(&[2, 1, 4, 5, 3, 3, 1]),
+            8 => Some(&[2, 3, 5, 7, 3, 1, 6, 1]),
+            _ => None,
+        }
+    }
+
+    pub fn circulant_mds(t: usize) -> Option<Vec<Vec<F>>> {
+        let row = Self::circulant_row(t)?;
+        Some(
+            (0..t)
+                .map(|i| {
+                    (0..t)
+                        .map(|j| utils::from_u64(row[(j + t - i) % t]))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, t: usize, rounds: usize) -> Vec<Vec<F>> {
+        (0..=rounds)
             .map(|_| {
-                (0..Self::T)
+                (0..t)
                     .map(|_| utils::field_element_from_shake(shake))
                     .collect()
             })
@@ -92,10 +590,225 @@ impl<F: PrimeField> ReinforcedConcreteParams<F> {
     }
 
     pub fn get_t(&self) -> usize {
-        Self::T
+        self.t
     }
 
     pub fn get_rounds(&self) -> usize {
-        Self::TOTAL_ROUNDS
+        self.pre_rounds + self.post_rounds + 1
+    }
+
+    pub fn get_pre_rounds(&self) -> usize {
+        self.pre_rounds
+    }
+
+    pub fn get_post_rounds(&self) -> usize {
+        self.post_rounds
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_params_tests_bn256 {
+    use crate::{
+        fields::bn256::FpBN256, reinforced_concrete::reinforced_concrete_instances::*,
+        utils::is_mds,
+    };
+
+    use ff::Field;
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    #[test]
+    fn validate() {
+        assert_eq!(RC_BN_PARAMS.validate(), Ok(()));
+        assert_eq!(RC_BN_PARAMS_T4.validate(), Ok(()));
+        assert_eq!(RC_BN_PARAMS_T5.validate(), Ok(()));
+        assert_eq!(RC_BN_PARAMS_T8.validate(), Ok(()));
+        assert!(
+            ReinforcedConcreteParams::<Scalar>::try_new(5, &BN256_SI, &BN256_SBOX, &*BN256_AB)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        type Params = ReinforcedConcreteParams<Scalar>;
+        type Error = ReinforcedConcreteParamsError;
+
+        let err = |d, si: &[u16], sbox: &[u16], ab: &[u16]| Params::try_new(d, si, sbox, ab).err();
+
+        assert_eq!(
+            err(5, &BN256_SI, &BN256_SBOX, &[1, 3, 2]),
+            Some(Error::InvalidAbLength(3))
+        );
+        assert_eq!(
+            err(5, &[], &BN256_SBOX, &*BN256_AB),
+            Some(Error::EmptyDecomposition)
+        );
+        assert_eq!(
+            err(5, &BN256_SI[1..], &BN256_SBOX, &*BN256_AB),
+            Some(Error::DecompositionTooSmall)
+        );
+        let mut si = BN256_SI.to_owned();
+        si[3] = 0;
+        assert_eq!(err(5, &si, &[], &*BN256_AB), Some(Error::InvalidDivisor(0)));
+        si[3] = 1;
+        assert_eq!(err(5, &si, &[], &*BN256_AB), Some(Error::InvalidDivisor(1)));
+        assert_eq!(
+            err(5, &[1000; 60], &BN256_SBOX, &*BN256_AB),
+            Some(Error::TooManyComposeGroups(10))
+        );
+        assert_eq!(
+            err(5, &BN256_SI, &BN256_SBOX, &[1; 16]),
+            Some(Error::UnsupportedWidth(9))
+        );
+        assert_eq!(
+            err(3, &BN256_SI, &BN256_SBOX, &*BN256_AB),
+            Some(Error::NonInvertibleExponent(3))
+        );
+        assert_eq!(
+            err(5, &BN256_SI, &BN256_SBOX, &[1, 3, 2, 2]),
+            Some(Error::SquareDiscriminant(1))
+        );
+
+        let mut sbox = BN256_SBOX.to_owned();
+        sbox[0] = sbox[1];
+        assert_eq!(
+            err(5, &BN256_SI, &sbox, &*BN256_AB),
+            Some(Error::SboxNotPermutation)
+        );
+
+        let sbox: Vec<u16> = (0..645).rev().collect();
+        assert_eq!(
+            err(5, &BN256_SI, &sbox, &*BN256_AB),
+            Some(Error::SboxExceedsDigits {
+                v: 645,
+                min_digit: 642
+            })
+        );
+
+        let sbox: Vec<u16> = (0..652).rev().collect();
+        assert_eq!(
+            err(5, &BN256_SI, &sbox, &*BN256_AB),
+            Some(Error::SboxTooLarge {
+                v: 652,
+                min_si: 651
+            })
+        );
+    }
+
+    #[test]
+    fn mds() {
+        for t in 3..=8 {
+            let mds = ReinforcedConcreteParams::<Scalar>::circulant_mds(t).unwrap();
+            assert!(is_mds(&mds));
+        }
+        assert!(ReinforcedConcreteParams::<Scalar>::circulant_mds(9).is_none());
+
+        for params in [&*RC_BN_PARAMS, &*RC_BN_PARAMS_T4, &*RC_BN_PARAMS_T8] {
+            let t = params.get_t();
+            for i in 0..t {
+                for j in 0..t {
+                    let mut sum = Scalar::zero();
+                    for k in 0..t {
+                        let mut tmp = params.mds[i][k];
+                        tmp.mul_assign(&params.mds_inv[k][j]);
+                        sum.add_assign(&tmp);
+                    }
+                    let expected = if i == j {
+                        Scalar::one()
+                    } else {
+                        Scalar::zero()
+                    };
+                    assert_eq!(sum, expected);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_params_tests_bls12 {
+    use crate::{
+        fields::bls12::FpBLS12, reinforced_concrete::reinforced_concrete_instances::*,
+        utils::is_mds,
+    };
+
+    use ff::Field;
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    #[test]
+    fn validate() {
+        assert_eq!(RC_BLS_PARAMS.validate(), Ok(()));
+        assert_eq!(RC_BLS_PARAMS_T4.validate(), Ok(()));
+        assert_eq!(RC_BLS_PARAMS_T5.validate(), Ok(()));
+        assert_eq!(RC_BLS_PARAMS_T8.validate(), Ok(()));
+        assert!(
+            ReinforcedConcreteParams::<Scalar>::try_new(5, &BLS12_SI, &BLS12_SBOX, &*BLS12_AB)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        type Params = ReinforcedConcreteParams<Scalar>;
+        type Error = ReinforcedConcreteParamsError;
+
+        let err = |d, si: &[u16], sbox: &[u16], ab: &[u16]| Params::try_new(d, si, sbox, ab).err();
+
+        assert_eq!(
+            err(5, &BLS12_SI[1..], &BLS12_SBOX, &*BLS12_AB),
+            Some(Error::DecompositionTooSmall)
+        );
+        assert_eq!(
+            err(5, &BLS12_SI, &BLS12_SBOX, &[1; 16]),
+            Some(Error::UnsupportedWidth(9))
+        );
+        assert_eq!(
+            err(3, &BLS12_SI, &BLS12_SBOX, &*BLS12_AB),
+            Some(Error::NonInvertibleExponent(3))
+        );
+
+        let sbox: Vec<u16> = (0..665).rev().collect();
+        assert_eq!(
+            err(5, &BLS12_SI, &sbox, &*BLS12_AB),
+            Some(Error::SboxExceedsDigits {
+                v: 665,
+                min_digit: 660
+            })
+        );
+    }
+
+    #[test]
+    fn mds() {
+        for t in 3..=8 {
+            let mds = ReinforcedConcreteParams::<Scalar>::circulant_mds(t).unwrap();
+            assert!(is_mds(&mds));
+        }
+        assert!(ReinforcedConcreteParams::<Scalar>::circulant_mds(9).is_none());
+
+        for params in [&*RC_BLS_PARAMS, &*RC_BLS_PARAMS_T4, &*RC_BLS_PARAMS_T8] {
+            let t = params.get_t();
+            for i in 0..t {
+                for j in 0..t {
+                    let mut sum = Scalar::zero();
+                    for k in 0..t {
+                        let mut tmp = params.mds[i][k];
+                        tmp.mul_assign(&params.mds_inv[k][j]);
+                        sum.add_assign(&tmp);
+                    }
+                    let expected = if i == j {
+                        Scalar::one()
+                    } else {
+                        Scalar::zero()
+                    };
+                    assert_eq!(sum, expected);
+                }
+            }
+        }
     }
 }
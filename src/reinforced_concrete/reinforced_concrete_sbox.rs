@@ -0,0 +1,199 @@
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake128,
+};
+use std::f64::consts::PI;
+
+// Quality criteria for the Bars S-box, which is a permutation of [0, v).
+// Differences and the linear approximations are considered over Z_v.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SboxCriteria {
+    pub max_differential_uniformity: usize,
+    pub max_linearity: f64,
+}
+
+impl SboxCriteria {
+    // Bounds a random permutation of [0, v) satisfies with a reasonable
+    // probability, i.e., rejects the weaker tail of the distribution
+    pub fn default_for(v: u16) -> Self {
+        SboxCriteria {
+            max_differential_uniformity: 8,
+            max_linearity: 1.5 * f64::sqrt(v as f64 * f64::ln(v as f64)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SboxSynthesizer {
+    v: u16,
+    criteria: SboxCriteria,
+    seed: Vec<u8>,
+}
+
+impl SboxSynthesizer {
+    pub const INIT_SHAKE: &'static str = "ReinforcedConcreteSbox";
+
+    pub fn new(v: u16, criteria: &SboxCriteria, seed: &[u8]) -> Self {
+        assert!(v > 2);
+        SboxSynthesizer {
+            v,
+            criteria: criteria.to_owned(),
+            seed: seed.to_owned(),
+        }
+    }
+
+    // Draws candidates with a Fisher-Yates shuffle from SHAKE until the first
+    // one satisfies all criteria. Returns the S-box and the number of rejected
+    // candidates.
+    pub fn synthesize(&self) -> (Vec<u16>, usize) {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        shake.update(&u16::to_le_bytes(self.v));
+        shake.update(&self.seed);
+        let mut reader = shake.finalize_xof();
+
+        let mut rejected = 0;
+        loop {
+            let sbox = self.shuffle(&mut reader);
+            if Self::fixed_points(&sbox) == 0
+                && Self::differential_uniformity(&sbox) <= self.criteria.max_differential_uniformity
+                && Self::linearity(&sbox) <= self.criteria.max_linearity
+            {
+                return (sbox, rejected);
+            }
+            rejected += 1;
+        }
+    }
+
+    fn shuffle(&self, reader: &mut dyn XofReader) -> Vec<u16> {
+        let mut sbox: Vec<u16> = (0..self.v).collect();
+        for i in (1..self.v as usize).rev() {
+            let j = Self::random_below(reader, i as u16 + 1);
+            sbox.swap(i, j as usize);
+        }
+        sbox
+    }
+
+    fn random_below(reader: &mut dyn XofReader, max: u16) -> u16 {
+        let limit = u16::MAX - u16::MAX % max;
+        let mut buf = [0u8; 2];
+        loop {
+            reader.read(&mut buf);
+            let r = u16::from_le_bytes(buf);
+            if r < limit {
+                return r % max;
+            }
+        }
+    }
+
+    pub fn fixed_points(sbox: &[u16]) -> usize {
+        sbox.iter()
+            .enumerate()
+            .filter(|(i, s)| *i == **s as usize)
+            .count()
+    }
+
+    // max_{a != 0, b} #{x | S(x + a) - S(x) = b}
+    pub fn differential_uniformity(sbox: &[u16]) -> usize {
+        let v = sbox.len();
+        let mut count = vec![0usize; v];
+        let mut max = 0;
+        for a in 1..v {
+            count.iter_mut().for_each(|c| *c = 0);
+            for x in 0..v {
+                let xa = if x + a >= v { x + a - v } else { x + a };
+                let diff = (sbox[xa] as usize + v - sbox[x] as usize) % v;
+                count[diff] += 1;
+            }
+            max = usize::max(max, *count.iter().max().unwrap());
+        }
+        max
+    }
+
+    // max_{a, b != 0} |sum_x w^(a * x - b * S(x))| with w = exp(2 pi i / v).
+    // Since the coefficients of (a, b) and (-a, -b) are conjugates, only half
+    // of the b's have to be considered.
+    pub fn linearity(sbox: &[u16]) -> f64 {
+        let v = sbox.len();
+        let cos: Vec<f64> = (0..v)
+            .map(|k| f64::cos(2f64 * PI * k as f64 / v as f64))
+            .collect();
+        let sin: Vec<f64> = (0..v)
+            .map(|k| f64::sin(2f64 * PI * k as f64 / v as f64))
+            .collect();
+
+        let mut bs = vec![0usize; v];
+        let mut max = 0f64;
+        for b in 1..=v / 2 {
+            for (bs_, s) in bs.iter_mut().zip(sbox.iter()) {
+                *bs_ = (b * *s as usize) % v;
+            }
+            for a in 1..v {
+                let mut re = 0f64;
+                let mut im = 0f64;
+                let mut ax = 0;
+                for bs_ in bs.iter() {
+                    let index = if ax >= *bs_ { ax - bs_ } else { ax + v - bs_ };
+                    re += cos[index];
+                    im += sin[index];
+                    ax += a;
+                    if ax >= v {
+                        ax -= v;
+                    }
+                }
+                max = f64::max(max, re * re + im * im);
+            }
+        }
+        max.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_sbox_tests {
+    use crate::reinforced_concrete::reinforced_concrete_instances::{BLS12_SBOX, BN256_SBOX};
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn synthesize() {
+        let v = 101;
+        let criteria = SboxCriteria::default_for(v);
+        for i in 0..TESTRUNS {
+            let synth = SboxSynthesizer::new(v, &criteria, &[i as u8]);
+            let (sbox, rejected) = synth.synthesize();
+            assert_eq!(synth.synthesize(), (sbox.to_owned(), rejected));
+
+            let mut sorted = sbox.to_owned();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..v).collect::<Vec<u16>>());
+            assert_eq!(SboxSynthesizer::fixed_points(&sbox), 0);
+            assert!(
+                SboxSynthesizer::differential_uniformity(&sbox)
+                    <= criteria.max_differential_uniformity
+            );
+            assert!(SboxSynthesizer::linearity(&sbox) <= criteria.max_linearity);
+        }
+    }
+
+    #[test]
+    fn criteria() {
+        // linear maps are the worst case for both metrics
+        let v = 61;
+        let sbox: Vec<u16> = (0..v).map(|x| (3 * x + 1) % v).collect();
+        assert_eq!(SboxSynthesizer::fixed_points(&sbox), 1);
+        assert_eq!(SboxSynthesizer::differential_uniformity(&sbox), v as usize);
+        assert!((SboxSynthesizer::linearity(&sbox) - v as f64).abs() < 1e-6);
+    }
+
+    #[test]
+    fn shipped_sboxes() {
+        // The shipped tables were not produced by the synthesizer and cannot
+        // be reproduced by it, since they contain fixed points.
+        assert_eq!(SboxSynthesizer::fixed_points(&BN256_SBOX), 1);
+        assert_eq!(SboxSynthesizer::fixed_points(&BLS12_SBOX), 3);
+        assert_eq!(SboxSynthesizer::differential_uniformity(&BN256_SBOX), 9);
+        assert_eq!(SboxSynthesizer::differential_uniformity(&BLS12_SBOX), 8);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{
+    reinforced_concrete::ReinforcedConcrete, reinforced_concrete_params::ReinforcedConcreteParams,
+};
+use crate::sponge::sponge_fp::{Sponge, SpongePermutation};
+use ff::PrimeField;
+use std::sync::Arc;
+
+// Sponge on top of the ReinforcedConcrete permutation, see sponge::sponge_fp::Sponge
+pub type ReinforcedConcreteSponge<F, const T: usize = 3> = Sponge<F, ReinforcedConcrete<F, T>, T>;
+
+impl<F: PrimeField, const T: usize> SpongePermutation<F, T> for ReinforcedConcrete<F, T> {
+    fn permutation(&self, input: &[F; T]) -> [F; T] {
+        ReinforcedConcrete::permutation(self, input)
+    }
+}
+
+impl<F: PrimeField> ReinforcedConcreteSponge<F> {
+    pub fn new(params: &Arc<ReinforcedConcreteParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> ReinforcedConcreteSponge<F, T> {
+    pub fn new_wide(params: &Arc<ReinforcedConcreteParams<F>>) -> Self {
+        Self::with_permutation(ReinforcedConcrete::new_wide(params))
+    }
+}
+
+impl<F: PrimeField, const T: usize> ReinforcedConcrete<F, T> {
+    pub fn hash_slice(&self, input: &[F]) -> F {
+        ReinforcedConcreteSponge::<F, T>::hash(self.clone(), input)
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_sponge_tests_bn256 {
+    use ff::from_hex;
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        reinforced_concrete::reinforced_concrete_instances::RC_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    #[test]
+    fn kats() {
+        let rc = ReinforcedConcrete::new(&RC_BN_PARAMS);
+        let input: Vec<Scalar> = (0..5).map(utils::from_u64).collect();
+        let hash = rc.hash_slice(&input);
+        assert_eq!(
+            hash,
+            from_hex("0x265ab973050111db6e6486fee2caf83c0cf708510f532be75f98dbafe154c7c4").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod reinforced_concrete_sponge_tests_bls12 {
+    use ff::from_hex;
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        reinforced_concrete::reinforced_concrete_instances::RC_BLS_PARAMS,
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    #[test]
+    fn kats() {
+        let rc = ReinforcedConcrete::new(&RC_BLS_PARAMS);
+        let input: Vec<Scalar> = (0..5).map(utils::from_u64).collect();
+        let hash = rc.hash_slice(&input);
+        assert_eq!(
+            hash,
+            from_hex("0x0f2f81180a8ac55cfe6470a88096427190f7a897dae4e4d06717d66315144364").unwrap()
+        );
+    }
+}
@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod rescue_prime;
+pub mod rescue_prime_instances;
+pub mod rescue_prime_params;
+pub mod rescue_prime_sponge;
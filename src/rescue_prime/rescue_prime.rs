@@ -0,0 +1,295 @@
+use super::rescue_prime_params::RescuePrimeParams;
+use crate::{fields::utils, merkle_tree::merkle_tree_fp::MerkleTreeHash};
+use ff::PrimeField;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct RescuePrime<F: PrimeField, const T: usize = 3> {
+    pub(crate) params: Arc<RescuePrimeParams<F>>,
+}
+
+impl<F: PrimeField> RescuePrime<F> {
+    pub fn new(params: &Arc<RescuePrimeParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> RescuePrime<F, T> {
+    // The width has to match the one of the parameters, e.g.,
+    // RescuePrime::<F, 5>::new_wide(&params) for 4-ary Merkle trees
+    pub fn new_wide(params: &Arc<RescuePrimeParams<F>>) -> Self {
+        assert!(params.get_t() == T);
+        RescuePrime {
+            params: Arc::clone(params),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        T
+    }
+
+    // Each round consists of two half rounds, the first one with x^d and the
+    // second one with x^(1/d), each followed by the MDS matrix and the
+    // addition of round constants.
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = input.to_owned();
+        for r in 0..self.params.rounds {
+            state.iter_mut().for_each(|el| *el = self.sbox(el));
+            self.linear_layer(&mut state, 2 * r);
+            state.iter_mut().for_each(|el| *el = self.sbox_inv(el));
+            self.linear_layer(&mut state, 2 * r + 1);
+        }
+        state
+    }
+
+    pub fn sbox(&self, input: &F) -> F {
+        utils::pow_small(input, self.params.d, &self.params.d_chain)
+    }
+
+    pub fn sbox_inv(&self, input: &F) -> F {
+        input.pow(self.params.d_inv)
+    }
+
+    pub fn linear_layer(&self, state: &mut [F; T], half_round: usize) {
+        let mut out = [F::zero(); T];
+        for ((row, o), rc) in self
+            .params
+            .mds
+            .iter()
+            .zip(out.iter_mut())
+            .zip(self.params.round_constants[half_round].iter())
+        {
+            for (m, i) in row.iter().zip(state.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                o.add_assign(&tmp);
+            }
+            o.add_assign(rc);
+        }
+        *state = out;
+    }
+
+    pub fn hash(&self, el1: &F, el2: &F) -> F {
+        let mut input = [F::zero(); T];
+        input[0] = el1.to_owned();
+        input[1] = el2.to_owned();
+        self.permutation(&input)[0]
+    }
+
+    // Compresses up to T - 1 elements, e.g., the children of a node in a
+    // (T - 1)-ary Merkle tree. The last element is used as capacity.
+    pub fn hash_n(&self, input: &[F]) -> F {
+        assert!(input.len() < T);
+        let mut state = [F::zero(); T];
+        state[..input.len()].copy_from_slice(input);
+        self.permutation(&state)[0]
+    }
+}
+
+impl<F: PrimeField, const T: usize> MerkleTreeHash<F> for RescuePrime<F, T> {
+    fn compress(&self, input: &[&F; 2]) -> F {
+        self.hash(input[0], input[1])
+    }
+}
+
+#[cfg(test)]
+mod rescue_prime_tests_bn256 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        rescue_prime::rescue_prime_instances::{RESCUE_PRIME_BN_PARAMS, RESCUE_PRIME_BN_PARAMS_T5},
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = rescue.permutation(&input1);
+            let perm2 = rescue.permutation(&input1);
+            let perm3 = rescue.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn sbox_inverse() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BN_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            assert_eq!(rescue.sbox_inv(&rescue.sbox(&input)), input);
+            assert_eq!(rescue.sbox(&rescue.sbox_inv(&input)), input);
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let rescue = RescuePrime::<Scalar, 5>::new_wide(&RESCUE_PRIME_BN_PARAMS_T5);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 5];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(rescue.hash_n(&input), rescue.permutation(&state)[0]);
+            assert_eq!(
+                rescue.compress(&[&input[0], &input[1]]),
+                rescue.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BN_PARAMS);
+        let input: [Scalar; 3] = [Scalar::zero(), Scalar::one(), utils::from_u64::<Scalar>(2)];
+        let perm = rescue.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x0ea3a75263ae75f19141d2b33b03cc783464e51eeb672205e2d845b16d9ffb3e").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x171494cccf5cf9935e016ca3f90b566d9df94f8339193d0fe6be3c58407149f0").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x1853c62e3b471459bf80dba86ed0aa4330a63943ce658a5bfdb6531e61f3cc23").unwrap()
+        );
+
+        let rescue = RescuePrime::<Scalar, 5>::new_wide(&RESCUE_PRIME_BN_PARAMS_T5);
+        let mut input = [Scalar::zero(); 5];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            rescue.permutation(&input)[0],
+            from_hex("0x26f8a111fdaa66b47738da7daa2f3b66229f2b69789be4f163efda7e815cdd0a").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod rescue_prime_tests_bls12 {
+    use ff::{from_hex, Field};
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        rescue_prime::rescue_prime_instances::{
+            RESCUE_PRIME_BLS_PARAMS, RESCUE_PRIME_BLS_PARAMS_T5,
+        },
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    #[test]
+    fn consistent_perm() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input1: [Scalar; 3] = [
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+                utils::random_scalar(true),
+            ];
+
+            let mut input2: [Scalar; 3];
+            loop {
+                input2 = [
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                    utils::random_scalar(true),
+                ];
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = rescue.permutation(&input1);
+            let perm2 = rescue.permutation(&input1);
+            let perm3 = rescue.permutation(&input2);
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    #[test]
+    fn sbox_inverse() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BLS_PARAMS);
+        for _ in 0..TESTRUNS {
+            let input: Scalar = utils::random_scalar(true);
+            assert_eq!(rescue.sbox_inv(&rescue.sbox(&input)), input);
+            assert_eq!(rescue.sbox(&rescue.sbox_inv(&input)), input);
+        }
+    }
+
+    #[test]
+    fn consistent_hash() {
+        let rescue = RescuePrime::<Scalar, 5>::new_wide(&RESCUE_PRIME_BLS_PARAMS_T5);
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..4).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [Scalar::zero(); 5];
+            state[..4].copy_from_slice(&input);
+            assert_eq!(rescue.hash_n(&input), rescue.permutation(&state)[0]);
+            assert_eq!(
+                rescue.compress(&[&input[0], &input[1]]),
+                rescue.hash_n(&input[..2])
+            );
+        }
+    }
+
+    #[test]
+    fn kats() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BLS_PARAMS);
+        let input: [Scalar; 3] = [Scalar::zero(), Scalar::one(), utils::from_u64::<Scalar>(2)];
+        let perm = rescue.permutation(&input);
+        assert_eq!(
+            perm[0],
+            from_hex("0x19d82ae2e6e3dcf6b15c736e13982a264e211268aaa8accc25270746bee62473").unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            from_hex("0x3d754c8989ca58df2ebf98dd0aace4666636e5afb960313b5d8945ee39e145c5").unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            from_hex("0x36ffc8f705d3af61a5529e3c184809e18dea28076b534ec310479d08128eeacc").unwrap()
+        );
+
+        let rescue = RescuePrime::<Scalar, 5>::new_wide(&RESCUE_PRIME_BLS_PARAMS_T5);
+        let mut input = [Scalar::zero(); 5];
+        for (i, el) in input.iter_mut().enumerate() {
+            *el = utils::from_u64(i as u64);
+        }
+        assert_eq!(
+            rescue.permutation(&input)[0],
+            from_hex("0x128d230e036974179e950edad39e2ff3c532f586c2a67a5533693eb26c393f53").unwrap()
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+use crate::{
+    fields::{bls12::FpBLS12, bn256::FpBN256},
+    rescue_prime::rescue_prime_params::RescuePrimeParams,
+};
+
+// x^5 with the number of rounds given by the Rescue-Prime round number
+// formula for 128 bit security and a capacity of one element
+lazy_static! {
+    // BLS12
+    pub static ref RESCUE_PRIME_BLS_PARAMS: Arc<RescuePrimeParams<FpBLS12>> =
+        Arc::new(RescuePrimeParams::new(3, 5, 14));
+    pub static ref RESCUE_PRIME_BLS_PARAMS_T5: Arc<RescuePrimeParams<FpBLS12>> =
+        Arc::new(RescuePrimeParams::new(5, 5, 9));
+
+    // BN256
+    pub static ref RESCUE_PRIME_BN_PARAMS: Arc<RescuePrimeParams<FpBN256>> =
+        Arc::new(RescuePrimeParams::new(3, 5, 14));
+    pub static ref RESCUE_PRIME_BN_PARAMS_T5: Arc<RescuePrimeParams<FpBN256>> =
+        Arc::new(RescuePrimeParams::new(5, 5, 9));
+}
@@ -0,0 +1,87 @@
+use crate::fields::utils;
+use ff::PrimeField;
+use sha3::{
+    digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, Update, XofReader},
+    Shake128, Shake128ReaderCore,
+};
+
+#[derive(Clone, Debug)]
+pub struct RescuePrimeParams<F: PrimeField> {
+    pub(crate) t: usize,
+    pub(crate) d: usize,
+    pub(crate) d_chain: Vec<(usize, usize)>,
+    pub(crate) d_inv: F::Repr,
+    pub(crate) rounds: usize,
+    pub(crate) mds: Vec<Vec<F>>,
+    // two constant vectors per round, one after each half round
+    pub(crate) round_constants: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> RescuePrimeParams<F> {
+    pub const INIT_SHAKE: &'static str = "RescuePrime";
+
+    pub fn new(t: usize, d: usize, rounds: usize) -> Self {
+        assert!(t >= 2 && rounds >= 1);
+        assert!(
+            d >= 2 && d <= u16::MAX as usize && utils::gcd_p_minus_1::<F>(d as u16) == 1,
+            "x^{} is not a permutation",
+            d
+        );
+
+        let mut shake = Self::init_shake();
+        let round_constants = Self::instantiate_rc(&mut shake, t, 2 * rounds);
+
+        RescuePrimeParams {
+            t,
+            d,
+            d_chain: utils::addition_chain(d as u64),
+            d_inv: utils::mod_inverse::<F>(d as u16, &utils::p_minus_1::<F>()),
+            rounds,
+            mds: Self::cauchy_mds(t),
+            round_constants,
+        }
+    }
+
+    fn init_shake() -> XofReaderCoreWrapper<Shake128ReaderCore> {
+        let mut shake = Shake128::default();
+        shake.update(Self::INIT_SHAKE.as_bytes());
+        for i in F::char().as_ref() {
+            shake.update(&u64::to_le_bytes(*i));
+        }
+        shake.finalize_xof()
+    }
+
+    fn instantiate_rc(shake: &mut dyn XofReader, t: usize, half_rounds: usize) -> Vec<Vec<F>> {
+        (0..half_rounds)
+            .map(|_| {
+                (0..t)
+                    .map(|_| utils::field_element_from_shake(shake))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Cauchy matrix M[i][j] = 1 / (x_i + y_j) with x_i = i and y_j = t + j. All
+    // x_i + y_j are distinct and non-zero, hence every minor is non-singular.
+    fn cauchy_mds(t: usize) -> Vec<Vec<F>> {
+        (0..t)
+            .map(|i| {
+                (0..t)
+                    .map(|j| utils::from_u64::<F>((t + i + j) as u64).inverse().unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn get_d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get_rounds(&self) -> usize {
+        self.rounds
+    }
+}
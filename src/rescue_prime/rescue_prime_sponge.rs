@@ -0,0 +1,81 @@
+use super::{rescue_prime::RescuePrime, rescue_prime_params::RescuePrimeParams};
+use crate::sponge::sponge_fp::{Sponge, SpongePermutation};
+use ff::PrimeField;
+use std::sync::Arc;
+
+// Sponge on top of the Rescue-Prime permutation, see sponge::sponge_fp::Sponge
+pub type RescuePrimeSponge<F, const T: usize = 3> = Sponge<F, RescuePrime<F, T>, T>;
+
+impl<F: PrimeField, const T: usize> SpongePermutation<F, T> for RescuePrime<F, T> {
+    fn permutation(&self, input: &[F; T]) -> [F; T] {
+        RescuePrime::permutation(self, input)
+    }
+}
+
+impl<F: PrimeField> RescuePrimeSponge<F> {
+    pub fn new(params: &Arc<RescuePrimeParams<F>>) -> Self {
+        Self::new_wide(params)
+    }
+}
+
+impl<F: PrimeField, const T: usize> RescuePrimeSponge<F, T> {
+    pub fn new_wide(params: &Arc<RescuePrimeParams<F>>) -> Self {
+        Self::with_permutation(RescuePrime::new_wide(params))
+    }
+}
+
+impl<F: PrimeField, const T: usize> RescuePrime<F, T> {
+    pub fn hash_slice(&self, input: &[F]) -> F {
+        RescuePrimeSponge::<F, T>::hash(self.clone(), input)
+    }
+}
+
+#[cfg(test)]
+mod rescue_prime_sponge_tests_bn256 {
+    use ff::from_hex;
+
+    use crate::{
+        fields::{bn256::FpBN256, utils},
+        rescue_prime::rescue_prime_instances::RESCUE_PRIME_BN_PARAMS,
+    };
+
+    type Scalar = FpBN256;
+
+    use super::*;
+
+    #[test]
+    fn kats() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BN_PARAMS);
+        let input: Vec<Scalar> = (0..5).map(utils::from_u64).collect();
+        let hash = rescue.hash_slice(&input);
+        assert_eq!(
+            hash,
+            from_hex("0x1ee92d21e6e746e093797ef8023a20cf9067f0d3d7182b0bd39d6b6aca86dbf1").unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod rescue_prime_sponge_tests_bls12 {
+    use ff::from_hex;
+
+    use crate::{
+        fields::{bls12::FpBLS12, utils},
+        rescue_prime::rescue_prime_instances::RESCUE_PRIME_BLS_PARAMS,
+    };
+
+    type Scalar = FpBLS12;
+
+    use super::*;
+
+    #[test]
+    fn kats() {
+        let rescue = RescuePrime::new(&RESCUE_PRIME_BLS_PARAMS);
+        let input: Vec<Scalar> = (0..5).map(utils::from_u64).collect();
+        let hash = rescue.hash_slice(&input);
+        assert_eq!(
+            hash,
+            from_hex("0x65b95ea96b31432f1d8ff626240f83561e74c2a0855fa6e6fc57efb9a8725837").unwrap()
+        );
+    }
+}
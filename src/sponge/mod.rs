@@ -0,0 +1 @@
+pub mod sponge_fp;
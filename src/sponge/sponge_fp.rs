@@ -0,0 +1,182 @@
+use ff::PrimeField;
+use std::marker::PhantomData;
+
+pub trait SpongePermutation<F: PrimeField, const T: usize> {
+    fn permutation(&self, input: &[F; T]) -> [F; T];
+}
+
+// Sponge on top of a permutation of width T. The first T-1 state elements form
+// the rate, the last one the capacity. Inputs are padded by appending a single
+// one followed by zeros up to a multiple of the rate, which makes the padding
+// injective.
+#[derive(Clone, Debug)]
+pub struct Sponge<F: PrimeField, P: SpongePermutation<F, T>, const T: usize> {
+    perm: P,
+    state: [F; T],
+    buffer: Vec<F>,
+    squeezing: bool,
+    squeeze_index: usize,
+    field: PhantomData<F>,
+}
+
+impl<F: PrimeField, P: SpongePermutation<F, T>, const T: usize> Sponge<F, P, T> {
+    pub const RATE: usize = T - 1;
+    pub const CAPACITY: usize = 1;
+
+    pub fn with_permutation(perm: P) -> Self {
+        Sponge {
+            perm,
+            state: [F::zero(); T],
+            buffer: Vec::with_capacity(Self::RATE),
+            squeezing: false,
+            squeeze_index: 0,
+            field: PhantomData,
+        }
+    }
+
+    fn absorb_block(&mut self) {
+        debug_assert!(self.buffer.len() == Self::RATE);
+        for (s, b) in self.state.iter_mut().zip(self.buffer.iter()) {
+            s.add_assign(b);
+        }
+        self.buffer.clear();
+        self.state = self.perm.permutation(&self.state);
+    }
+
+    pub fn absorb(&mut self, input: &[F]) {
+        assert!(!self.squeezing, "cannot absorb after squeezing");
+        for el in input {
+            self.buffer.push(el.to_owned());
+            if self.buffer.len() == Self::RATE {
+                self.absorb_block();
+            }
+        }
+    }
+
+    fn finalize(&mut self) {
+        // pad with 10*
+        self.buffer.push(F::one());
+        self.buffer.resize(Self::RATE, F::zero());
+        self.absorb_block();
+        self.squeezing = true;
+        self.squeeze_index = 0;
+    }
+
+    pub fn squeeze(&mut self, num: usize) -> Vec<F> {
+        if !self.squeezing {
+            self.finalize();
+        }
+
+        let mut res = Vec::with_capacity(num);
+        for _ in 0..num {
+            if self.squeeze_index == Self::RATE {
+                self.state = self.perm.permutation(&self.state);
+                self.squeeze_index = 0;
+            }
+            res.push(self.state[self.squeeze_index]);
+            self.squeeze_index += 1;
+        }
+        res
+    }
+
+    // absorbs input and squeezes a single element
+    pub fn hash(perm: P, input: &[F]) -> F {
+        let mut sponge = Self::with_permutation(perm);
+        sponge.absorb(input);
+        sponge.squeeze(1)[0]
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::{
+        fields::{bls12::FpBLS12, bn256::FpBN256, utils},
+        reinforced_concrete::{
+            reinforced_concrete::ReinforcedConcrete,
+            reinforced_concrete_instances::{
+                RC_BLS_PARAMS, RC_BLS_PARAMS_T5, RC_BN_PARAMS, RC_BN_PARAMS_T5,
+            },
+        },
+        rescue_prime::{
+            rescue_prime::RescuePrime,
+            rescue_prime_instances::{
+                RESCUE_PRIME_BLS_PARAMS, RESCUE_PRIME_BLS_PARAMS_T5, RESCUE_PRIME_BN_PARAMS,
+                RESCUE_PRIME_BN_PARAMS_T5,
+            },
+        },
+    };
+
+    use super::*;
+
+    static TESTRUNS: usize = 5;
+
+    fn consistent_absorb<F: PrimeField, P: SpongePermutation<F, T> + Clone, const T: usize>(
+        perm: &P,
+    ) {
+        for len in 0..TESTRUNS * 2 {
+            let input: Vec<F> = (0..len).map(|_| utils::random_scalar(true)).collect();
+
+            let mut sponge1 = Sponge::with_permutation(perm.clone());
+            sponge1.absorb(&input);
+            let out1 = sponge1.squeeze(5);
+
+            let mut sponge2 = Sponge::with_permutation(perm.clone());
+            for el in input.iter() {
+                sponge2.absorb(&[el.to_owned()]);
+            }
+            let mut out2 = sponge2.squeeze(2);
+            out2.extend(sponge2.squeeze(3));
+
+            assert_eq!(out1, out2);
+        }
+    }
+
+    fn padding<F: PrimeField, P: SpongePermutation<F, T> + Clone, const T: usize>(perm: &P) {
+        let hash = |input: &[F]| Sponge::hash(perm.clone(), input);
+        for _ in 0..TESTRUNS {
+            let input: F = utils::random_scalar(true);
+            let h1 = hash(&[input]);
+            let h2 = hash(&[input, F::zero()]);
+            let h3 = hash(&[input, F::one()]);
+            assert_ne!(h1, h2);
+            assert_ne!(h1, h3);
+            assert_ne!(h2, h3);
+        }
+        assert_ne!(hash(&[]), hash(&[F::zero()]));
+    }
+
+    fn single_block<F: PrimeField, P: SpongePermutation<F, T> + Clone, const T: usize>(perm: &P) {
+        for len in 0..T - 1 {
+            let input: Vec<F> = (0..len).map(|_| utils::random_scalar(true)).collect();
+            let mut state = [F::zero(); T];
+            state[..len].copy_from_slice(&input);
+            state[len] = F::one();
+            assert_eq!(
+                perm.permutation(&state)[0],
+                Sponge::hash(perm.clone(), &input)
+            );
+        }
+    }
+
+    fn all<F: PrimeField, P: SpongePermutation<F, T> + Clone, const T: usize>(perm: &P) {
+        consistent_absorb(perm);
+        padding(perm);
+        single_block(perm);
+    }
+
+    #[test]
+    fn reinforced_concrete() {
+        all::<FpBN256, _, 3>(&ReinforcedConcrete::new(&RC_BN_PARAMS));
+        all::<FpBN256, _, 5>(&ReinforcedConcrete::new_wide(&RC_BN_PARAMS_T5));
+        all::<FpBLS12, _, 3>(&ReinforcedConcrete::new(&RC_BLS_PARAMS));
+        all::<FpBLS12, _, 5>(&ReinforcedConcrete::new_wide(&RC_BLS_PARAMS_T5));
+    }
+
+    #[test]
+    fn rescue_prime() {
+        all::<FpBN256, _, 3>(&RescuePrime::new(&RESCUE_PRIME_BN_PARAMS));
+        all::<FpBN256, _, 5>(&RescuePrime::new_wide(&RESCUE_PRIME_BN_PARAMS_T5));
+        all::<FpBLS12, _, 3>(&RescuePrime::new(&RESCUE_PRIME_BLS_PARAMS));
+        all::<FpBLS12, _, 5>(&RescuePrime::new_wide(&RESCUE_PRIME_BLS_PARAMS_T5));
+    }
+}
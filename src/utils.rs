@@ -92,6 +92,44 @@ pub fn mat_inverse<F: PrimeField>(mat: &[Vec<F>]) -> Vec<Vec<F>> {
     inv
 }
 
+// every square submatrix has to be non-singular
+#[cfg(test)]
+pub fn is_mds<F: PrimeField>(mat: &[Vec<F>]) -> bool {
+    let t = mat.len();
+    let subsets: Vec<Vec<usize>> = (1..1usize << t)
+        .map(|mask| (0..t).filter(|i| mask >> i & 1 == 1).collect())
+        .collect();
+    for rows in subsets.iter() {
+        for cols in subsets.iter().filter(|c| c.len() == rows.len()) {
+            let mut sub: Vec<Vec<F>> = rows
+                .iter()
+                .map(|r| cols.iter().map(|c| mat[*r][*c]).collect())
+                .collect();
+            // gaussian elimination
+            let n = sub.len();
+            for col in 0..n {
+                let pivot = match (col..n).find(|r| !sub[*r][col].is_zero()) {
+                    Some(pivot) => pivot,
+                    None => return false,
+                };
+                sub.swap(col, pivot);
+                // division free: row = pivot * row - factor * pivot_row
+                let pivot_row = sub[col].to_owned();
+                for row in sub.iter_mut().skip(col + 1) {
+                    let factor = row[col];
+                    for (el, p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                        let mut tmp = *p;
+                        tmp.mul_assign(&factor);
+                        el.mul_assign(&pivot_row[col]);
+                        el.sub_assign(&tmp);
+                    }
+                }
+            }
+        }
+    }
+    true
+}
+
 pub fn mat_transpose<F: PrimeField>(mat: &[Vec<F>]) -> Vec<Vec<F>> {
     let rows = mat.len();
     let cols = mat[0].len();
@@ -105,6 +143,39 @@ pub fn mat_transpose<F: PrimeField>(mat: &[Vec<F>]) -> Vec<Vec<F>> {
     transpose
 }
 
+pub fn mat_mat_mul<F: PrimeField>(mat1: &[Vec<F>], mat2: &[Vec<F>]) -> Vec<Vec<F>> {
+    let rows = mat1.len();
+    let cols = mat2[0].len();
+    assert!(mat1[0].len() == mat2.len());
+    let mut res = vec![vec![F::zero(); cols]; rows];
+
+    for (resrow, mat1row) in res.iter_mut().zip(mat1.iter()) {
+        for (col, el) in resrow.iter_mut().enumerate() {
+            for (m1, mat2row) in mat1row.iter().zip(mat2.iter()) {
+                let mut tmp = *m1;
+                tmp.mul_assign(&mat2row[col]);
+                el.add_assign(&tmp);
+            }
+        }
+    }
+    res
+}
+
+pub fn mat_vec_mul<F: PrimeField>(mat: &[Vec<F>], input: &[F]) -> Vec<F> {
+    assert!(mat[0].len() == input.len());
+    mat.iter()
+        .map(|row| {
+            let mut res = F::zero();
+            for (m, i) in row.iter().zip(input.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(i);
+                res.add_assign(&tmp);
+            }
+            res
+        })
+        .collect()
+}
+
 pub fn random_scalar_rng<F: PrimeField, R: Rng>(allow_zero: bool, rng: &mut R) -> F {
     loop {
         let s = F::rand(rng);